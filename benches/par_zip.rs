@@ -0,0 +1,68 @@
+//! Compares the sequential and parallel zip/unzip functions
+//!
+//! Run with `cargo bench --features rayon`
+//!
+//! The parallel versions only pay off with several cores (on a single thread they just add rayon's overhead),
+//! so the speedups printed here depend on the machine and are only meaningful on a multi-core one.
+
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+use tlib::vec_zip::{par_unzip8, par_zip8, unzip8, zip8};
+
+const ROWS: usize = 2_000_000;
+const RUNS: u32 = 5;
+
+// times only `f`: its input is built by `setup` beforehand, and its output is dropped afterwards
+fn bench<I, T>(name: &str, mut setup: impl FnMut() -> I, mut f: impl FnMut(I) -> T) -> Duration {
+    // warm-up
+    black_box(f(setup()));
+    let mut total = Duration::default();
+    for _ in 0..RUNS {
+        let input = setup();
+        let start = Instant::now();
+        let output = black_box(f(black_box(input)));
+        total += start.elapsed();
+        drop(output);
+    }
+    let avg = total / RUNS;
+    println!("{:<12} {:>10.2?}", name, avg);
+    avg
+}
+
+fn main() {
+    let col: Vec<u64> = (0..ROWS as u64).collect();
+    let cols = || {
+        (
+            col.clone(),
+            col.clone(),
+            col.clone(),
+            col.clone(),
+            col.clone(),
+            col.clone(),
+            col.clone(),
+            col.clone(),
+        )
+    };
+    let rows = {
+        let (a, b, c, d, e, f, g, h) = cols();
+        zip8(a, b, c, d, e, f, g, h)
+    };
+
+    println!(
+        "{} rows, 8 columns, {} threads",
+        ROWS,
+        rayon::current_num_threads()
+    );
+
+    let seq = bench("zip8", cols, |(a, b, c, d, e, f, g, h)| {
+        zip8(a, b, c, d, e, f, g, h)
+    });
+    let par = bench("par_zip8", cols, |(a, b, c, d, e, f, g, h)| {
+        par_zip8(a, b, c, d, e, f, g, h)
+    });
+    println!("speedup: {:.2}x", seq.as_secs_f64() / par.as_secs_f64());
+
+    let seq = bench("unzip8", || rows.clone(), unzip8);
+    let par = bench("par_unzip8", || rows.clone(), par_unzip8);
+    println!("speedup: {:.2}x", seq.as_secs_f64() / par.as_secs_f64());
+}
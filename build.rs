@@ -1,4 +1,5 @@
 fn main() {
+    println!("cargo:rustc-check-cfg=cfg(profile, values(\"debug\", \"release\"))");
     if let Ok(s) = std::env::var("PROFILE") {
         // values: debug, release
         println!("cargo:rustc-cfg=profile=\"{}\"", s);
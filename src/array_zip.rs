@@ -0,0 +1,109 @@
+// These work like the functions in `vec_zip`, but since the length is known at compile time,
+// they don't need to allocate and are therefore available without `use_std`.
+use core::array;
+
+/// Zips two arrays of the same length into an array of pairs
+///
+/// Example:
+/// ```
+/// # use tlib::array_zip::zip_arrays;
+/// assert_eq!(zip_arrays([1, 2, 3], ['a', 'b', 'c']), [(1, 'a'), (2, 'b'), (3, 'c')]);
+/// ```
+pub fn zip_arrays<A, B, const N: usize>(a: [A; N], b: [B; N]) -> [(A, B); N] {
+    let mut a = IntoIterator::into_iter(a);
+    let mut b = IntoIterator::into_iter(b);
+    // all iterators have exactly `N` items, so `next` can't fail
+    array::from_fn(|_| (a.next().unwrap(), b.next().unwrap()))
+}
+
+/// Unzips an array of pairs into two arrays
+///
+/// Example:
+/// ```
+/// # use tlib::array_zip::unzip_array;
+/// assert_eq!(unzip_array([(1, 'a'), (2, 'b'), (3, 'c')]), ([1, 2, 3], ['a', 'b', 'c']));
+/// ```
+pub fn unzip_array<A, B, const N: usize>(v: [(A, B); N]) -> ([A; N], [B; N]) {
+    let mut a: [Option<A>; N] = array::from_fn(|_| None);
+    let mut b: [Option<B>; N] = array::from_fn(|_| None);
+    for (i, (x, y)) in IntoIterator::into_iter(v).enumerate() {
+        a[i] = Some(x);
+        b[i] = Some(y);
+    }
+    // every slot was filled above
+    (a.map(Option::unwrap), b.map(Option::unwrap))
+}
+
+macro_rules! gen_array_fns {
+    (($zip:ident, $unzip:ident) => $($binding:ident @ $param:ident: $t:ident),+) => {
+        /// Like [`zip_arrays`](fn.zip_arrays.html) but with more arrays
+        #[allow(clippy::too_many_arguments)]
+        pub fn $zip<$($t),+, const N: usize>($($param: [$t; N]),+) -> [($($t),+); N] {
+            $(
+                let mut $param = IntoIterator::into_iter($param);
+            )+
+            array::from_fn(|_| ($($param.next().unwrap()),+))
+        }
+
+        /// Like [`unzip_array`](fn.unzip_array.html) but with more arrays
+        #[allow(clippy::type_complexity)]
+        pub fn $unzip<$($t),+, const N: usize>(v: [($($t),+); N]) -> ($([$t; N]),+) {
+            $(
+                let mut $param: [Option<$t>; N] = array::from_fn(|_| None);
+            )+
+            for (i, ($($binding),+)) in IntoIterator::into_iter(v).enumerate() {
+                $(
+                    $param[i] = Some($binding);
+                )+
+            }
+            ($($param.map(Option::unwrap)),+)
+        }
+    };
+}
+
+gen_array_fns!((zip_arrays3, unzip_array3) => t1 @ a: A, t2 @ b: B, t3 @ c: C);
+gen_array_fns!((zip_arrays4, unzip_array4) => t1 @ a: A, t2 @ b: B, t3 @ c: C, t4 @ d: D);
+gen_array_fns!((zip_arrays5, unzip_array5) => t1 @ a: A, t2 @ b: B, t3 @ c: C, t4 @ d: D, t5 @ e: E);
+gen_array_fns!((zip_arrays6, unzip_array6) => t1 @ a: A, t2 @ b: B, t3 @ c: C, t4 @ d: D, t5 @ e: E, t6 @ f: F);
+gen_array_fns!((zip_arrays7, unzip_array7) => t1 @ a: A, t2 @ b: B, t3 @ c: C, t4 @ d: D, t5 @ e: E, t6 @ f: F, t7 @ g: G);
+gen_array_fns!((zip_arrays8, unzip_array8) => t1 @ a: A, t2 @ b: B, t3 @ c: C, t4 @ d: D, t5 @ e: E, t6 @ f: F, t7 @ g: G, t8 @ h: H);
+gen_array_fns!((zip_arrays9, unzip_array9) => t1 @ a: A, t2 @ b: B, t3 @ c: C, t4 @ d: D, t5 @ e: E, t6 @ f: F, t7 @ g: G, t8 @ h: H, t9 @ i: I);
+gen_array_fns!((zip_arrays10, unzip_array10) => t1 @ a: A, t2 @ b: B, t3 @ c: C, t4 @ d: D, t5 @ e: E, t6 @ f: F, t7 @ g: G, t8 @ h: H, t9 @ i: I, t10 @ j: J);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn roundtrip() {
+        let a = [1, 2, 3];
+        let b = ['a', 'b', 'c'];
+        let c = ["x", "y", "z"];
+        let zipped = zip_arrays3(a, b, c);
+        assert_eq!(zipped, [(1, 'a', "x"), (2, 'b', "y"), (3, 'c', "z")]);
+        assert_eq!(unzip_array3(zipped), (a, b, c));
+
+        let empty: [(u8, u8); 0] = zip_arrays([], []);
+        assert_eq!(unzip_array(empty), ([], []));
+    }
+
+    #[test]
+    fn non_copy() {
+        use core::cell::Cell;
+
+        // counts drops to make sure nothing is duplicated or leaked
+        struct Tracked<'a>(&'a Cell<usize>);
+        impl Drop for Tracked<'_> {
+            fn drop(&mut self) {
+                self.0.set(self.0.get() + 1);
+            }
+        }
+
+        let drops = Cell::new(0);
+        let t = || Tracked(&drops);
+        let (a, b) = unzip_array(zip_arrays([t(), t()], [t(), t()]));
+        assert_eq!(drops.get(), 0);
+        drop((a, b));
+        assert_eq!(drops.get(), 4);
+    }
+}
@@ -140,7 +140,7 @@ impl CargoPackageInfo {
 
 #[allow(missing_docs)]
 impl CargoPackageInfo {
-    pub fn version(&self) -> CargoPackageVersion {
+    pub fn version(&self) -> CargoPackageVersion<'_> {
         self.version
     }
     pub fn name(&self) -> &'static str {
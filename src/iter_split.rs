@@ -1,9 +1,77 @@
 use crate::auto_escape::{
     indicator, indicator_not_escaped, unescape_all_except, AutoEscape, Unescape,
 };
-use std::iter::{once, FromIterator};
+use std::collections::VecDeque;
+use std::iter::{once, FromIterator, Rev};
 use std::marker::PhantomData;
 
+pub use self::borrowed::*;
+pub use self::streaming::*;
+
+mod borrowed;
+mod streaming;
+
+/// What to do with the separators when splitting
+///
+/// `false` and `true` convert to `Discard` and `Separate`
+/// (which is what the `keep_sep` flag used to mean)
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
+pub enum SepMode {
+    /// Drop the separators
+    Discard,
+    /// Emit the separators as segments of their own
+    Separate,
+    /// Attach each separator to the end of the segment before it (like `str::split_inclusive`)
+    AttachPrev,
+    /// Attach each separator to the start of the segment after it
+    AttachNext,
+}
+
+impl SepMode {
+    // the mode that has the same effect when splitting the reversed input
+    fn reversed(self) -> Self {
+        match self {
+            SepMode::AttachPrev => SepMode::AttachNext,
+            SepMode::AttachNext => SepMode::AttachPrev,
+            mode => mode,
+        }
+    }
+}
+
+impl From<bool> for SepMode {
+    fn from(keep_sep: bool) -> Self {
+        if keep_sep {
+            SepMode::Separate
+        } else {
+            SepMode::Discard
+        }
+    }
+}
+
+/// What to do with empty segments when splitting
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
+pub enum EmptySegments {
+    /// Keep all segments (e.g. `"a:"` gives `["a", ""]` and `""` gives `[""]`)
+    Keep,
+    /// Treat the separators as terminators, i.e. drop the last segment if it is empty
+    /// (e.g. `"a:"` gives `["a"]` and `""` gives `[]`), like `str::split_terminator`
+    Terminator,
+    /// Drop all empty segments (e.g. `"a::b:"` gives `["a", "b"]`)
+    Skip,
+}
+
+impl EmptySegments {
+    // whether a segment should be dropped
+    fn drops(self, is_empty: bool, is_last: bool) -> bool {
+        is_empty
+            && match self {
+                EmptySegments::Keep => false,
+                EmptySegments::Terminator => is_last,
+                EmptySegments::Skip => true,
+            }
+    }
+}
+
 /// An iterator for splitting another iterator by single items
 pub struct SplitIter<I: Iterator, F, V> {
     // (dyn) the number of split segments already returned
@@ -12,48 +80,334 @@ pub struct SplitIter<I: Iterator, F, V> {
     max_len: Option<usize>,
     iter: I,
     is_sep: F,
-    // (setting) whether to emit the separator into the stream
-    keep_sep: bool,
+    // (setting) what to do with the separators
+    sep_mode: SepMode,
     // (dyn) the separator, if it was kept from the last call to `next`
+    // (for `Separate` and `AttachNext`)
     // starts at None
     last_sep: Option<I::Item>,
+    // (setting) which empty segments to drop
+    empty: EmptySegments,
     // (dyn) this is set when no more `Some`s should be returned
     // starts at false
     done: bool,
     _marker: PhantomData<V>,
 }
 
+impl<I: Iterator, F, V> SplitIter<I, F, V> {
+    /// Sets what to do with empty segments (by default, they are all kept)
+    ///
+    /// Dropped segments don't count towards the limit of `splitn`
+    pub fn empty_segments(mut self, empty: EmptySegments) -> Self {
+        self.empty = empty;
+        self
+    }
+}
+
 impl<I: Iterator, F: FnMut(&I::Item) -> bool, V: FromIterator<I::Item>> Iterator
     for SplitIter<I, F, V>
 {
     type Item = V;
 
+    fn next(&mut self) -> Option<V> {
+        loop {
+            if self.done {
+                return None;
+            }
+            let mut res = Vec::new();
+            if let Some(sep) = self.last_sep.take() {
+                if self.sep_mode == SepMode::Separate {
+                    return Some(once(sep).collect());
+                }
+                res.push(sep);
+            }
+            // whether this is the last segment
+            let mut last = true;
+            if self.max_len == Some(self.curr_len + 1) {
+                // the length limit is reached: return the whole rest
+                res.extend(self.iter.by_ref());
+            } else {
+                for x in self.iter.by_ref() {
+                    if (self.is_sep)(&x) {
+                        match self.sep_mode {
+                            SepMode::Discard => {}
+                            SepMode::Separate | SepMode::AttachNext => self.last_sep = Some(x),
+                            SepMode::AttachPrev => res.push(x),
+                        }
+                        // after a sep, there has to be another segment (even if empty)
+                        last = false;
+                        break;
+                    }
+                    res.push(x);
+                }
+            }
+            self.done = last;
+            if !self.empty.drops(res.is_empty(), last) {
+                self.curr_len += 1;
+                return Some(res.into_iter().collect());
+            }
+        }
+    }
+}
+
+/// An iterator for splitting another iterator by single items, starting from the back
+///
+/// The segments are returned from last to first, but the items in each segment are in their original order
+pub struct RSplitIter<I: DoubleEndedIterator, F, V> {
+    inner: SplitIter<Rev<I>, F, Vec<I::Item>>,
+    // (setting/dyn) whether the first segment (the last one in the original order) should be dropped if it is empty
+    // (this can't be done by `inner`, since it sees it as the first one)
+    // starts at the setting, is reset after the first segment
+    drop_empty_first: bool,
+    _marker: PhantomData<V>,
+}
+
+impl<I: DoubleEndedIterator, F, V> RSplitIter<I, F, V> {
+    /// Sets what to do with empty segments (by default, they are all kept)
+    ///
+    /// `EmptySegments::Terminator` refers to the original order,
+    /// so it drops the *first* segment returned by this iterator if it is empty
+    /// (like `str::rsplit_terminator`)
+    pub fn empty_segments(mut self, empty: EmptySegments) -> Self {
+        self.drop_empty_first = empty == EmptySegments::Terminator;
+        self.inner.empty = match empty {
+            // the trailing segment is handled here, `inner` keeps everything else
+            EmptySegments::Terminator => EmptySegments::Keep,
+            other => other,
+        };
+        self
+    }
+}
+
+impl<I: DoubleEndedIterator, F: FnMut(&I::Item) -> bool, V: FromIterator<I::Item>> Iterator
+    for RSplitIter<I, F, V>
+{
+    type Item = V;
+
+    fn next(&mut self) -> Option<V> {
+        let mut v = self.inner.next()?;
+        if std::mem::take(&mut self.drop_empty_first) && v.is_empty() {
+            // the dropped segment shouldn't count towards the limit
+            if let Some(n) = &mut self.inner.max_len {
+                *n += 1;
+            }
+            v = self.inner.next()?;
+        }
+        Some(v.into_iter().rev().collect())
+    }
+}
+
+/// An iterator for splitting another iterator by a sequence of items
+pub struct SplitSeqIter<I: Iterator, V> {
+    // (dyn) the number of split segments already returned
+    // starts at 0
+    curr_len: usize,
+    max_len: Option<usize>,
+    iter: I,
+    sep: Vec<I::Item>,
+    // (setting) what to do with the separators
+    sep_mode: SepMode,
+    // (dyn) the lookahead, never longer than `sep`
+    // starts empty
+    window: VecDeque<I::Item>,
+    // (dyn) the separator, if it was kept from the last call to `next`
+    // (for `Separate` and `AttachNext`)
+    // starts at None
+    last_sep: Option<Vec<I::Item>>,
+    // (setting) which empty segments to drop
+    empty: EmptySegments,
+    // (dyn) this is set when no more `Some`s should be returned
+    // starts at false
+    done: bool,
+    _marker: PhantomData<V>,
+}
+
+impl<I: Iterator, V> SplitSeqIter<I, V> {
+    /// Sets what to do with empty segments (by default, they are all kept)
+    ///
+    /// Dropped segments don't count towards the limit of `splitn_seq`
+    pub fn empty_segments(mut self, empty: EmptySegments) -> Self {
+        self.empty = empty;
+        self
+    }
+}
+
+impl<I: Iterator, V> SplitSeqIter<I, V>
+where
+    I::Item: PartialEq,
+{
+    // moves items into `res` until the next separator,
+    // returning whether the end was reached instead
+    fn next_segment(&mut self, res: &mut Vec<I::Item>) -> bool {
+        loop {
+            while self.window.len() < self.sep.len() {
+                match self.iter.next() {
+                    Some(x) => self.window.push_back(x),
+                    None => break,
+                }
+            }
+            if self.window.iter().eq(self.sep.iter()) {
+                match self.sep_mode {
+                    SepMode::Discard => self.window.clear(),
+                    SepMode::Separate | SepMode::AttachNext => {
+                        self.last_sep = Some(self.window.drain(..).collect())
+                    }
+                    SepMode::AttachPrev => res.extend(self.window.drain(..)),
+                }
+                // after a sep, there has to be another segment (even if empty)
+                return false;
+            }
+            match self.window.pop_front() {
+                Some(x) => res.push(x),
+                None => return true,
+            }
+        }
+    }
+}
+
+impl<I: Iterator, V: FromIterator<I::Item>> Iterator for SplitSeqIter<I, V>
+where
+    I::Item: PartialEq,
+{
+    type Item = V;
+
+    fn next(&mut self) -> Option<V> {
+        loop {
+            if self.done {
+                return None;
+            }
+            let mut res = Vec::new();
+            if let Some(sep) = self.last_sep.take() {
+                if self.sep_mode == SepMode::Separate {
+                    return Some(sep.into_iter().collect());
+                }
+                res = sep;
+            }
+            // whether this is the last segment
+            let last = if self.max_len == Some(self.curr_len + 1) {
+                // the length limit is reached: return the whole rest
+                res.extend(self.window.drain(..).chain(self.iter.by_ref()));
+                true
+            } else {
+                self.next_segment(&mut res)
+            };
+            self.done = last;
+            if !self.empty.drops(res.is_empty(), last) {
+                self.curr_len += 1;
+                return Some(res.into_iter().collect());
+            }
+        }
+    }
+}
+
+/// An iterator for splitting another iterator between consecutive items
+pub struct SplitBetweenIter<I: Iterator, F, V> {
+    // (dyn) the number of split segments already returned
+    // starts at 0
+    curr_len: usize,
+    max_len: Option<usize>,
+    iter: I,
+    is_boundary: F,
+    // (dyn) the first item of the next segment
+    // starts at None
+    pending: Option<I::Item>,
+    // (dyn) this is set when no more `Some`s should be returned
+    // starts at false
+    done: bool,
+    _marker: PhantomData<V>,
+}
+
+impl<I: Iterator, F: FnMut(&I::Item, &I::Item) -> bool, V: FromIterator<I::Item>> Iterator
+    for SplitBetweenIter<I, F, V>
+{
+    type Item = V;
+
     fn next(&mut self) -> Option<V> {
         if self.done {
             return None;
         }
-        if let Some(sep) = self.last_sep.take() {
-            return Some(once(sep).collect());
+        let first = match self.pending.take().or_else(|| self.iter.next()) {
+            Some(x) => x,
+            None => {
+                self.done = true;
+                return None;
+            }
+        };
+        self.curr_len += 1;
+        if self.max_len == Some(self.curr_len) {
+            let v = once(first).chain(self.iter.by_ref()).collect();
+            self.done = true;
+            // the length limit is reached: return the whole rest
+            return Some(v);
         }
+        let mut res = vec![first];
+        for x in self.iter.by_ref() {
+            // `res` always contains at least `first`
+            if (self.is_boundary)(res.last().unwrap(), &x) {
+                self.pending = Some(x);
+                return Some(res.into_iter().collect());
+            }
+            res.push(x);
+        }
+        // reached the end
+        self.done = true;
+        Some(res.into_iter().collect())
+    }
+}
+
+/// An iterator for splitting another iterator into runs of items with the same key
+pub struct ChunkByIter<I: Iterator, G, K, V> {
+    // (dyn) the number of chunks already returned
+    // starts at 0
+    curr_len: usize,
+    max_len: Option<usize>,
+    iter: I,
+    key: G,
+    // (dyn) the first item of the next chunk, with its key
+    // starts at None
+    pending: Option<(K, I::Item)>,
+    // (dyn) this is set when no more `Some`s should be returned
+    // starts at false
+    done: bool,
+    _marker: PhantomData<V>,
+}
+
+impl<I: Iterator, G: FnMut(&I::Item) -> K, K: PartialEq, V: FromIterator<I::Item>> Iterator
+    for ChunkByIter<I, G, K, V>
+{
+    type Item = V;
+
+    fn next(&mut self) -> Option<V> {
+        if self.done {
+            return None;
+        }
+        let pending = match self.pending.take() {
+            Some(p) => Some(p),
+            None => self.iter.next().map(|x| ((self.key)(&x), x)),
+        };
+        let (curr_key, first) = match pending {
+            Some(p) => p,
+            None => {
+                self.done = true;
+                return None;
+            }
+        };
         self.curr_len += 1;
-        if self.max_len.map_or(false, |len| self.curr_len == len) {
-            let v = self.iter.by_ref().collect();
+        if self.max_len == Some(self.curr_len) {
+            let v = once(first).chain(self.iter.by_ref()).collect();
             self.done = true;
             // the length limit is reached: return the whole rest
             return Some(v);
         }
-        let mut res = Vec::new();
-        while let Some(x) = self.iter.next() {
-            if (self.is_sep)(&x) {
-                if self.keep_sep {
-                    self.last_sep = Some(x);
-                }
-                // return here, without setting `done`
-                // -> after a sep, there has to be another element (even if empty)
+        let mut res = vec![first];
+        for x in self.iter.by_ref() {
+            // every key is computed exactly once, and kept for the next chunk if it differs
+            let k = (self.key)(&x);
+            if k != curr_key {
+                self.pending = Some((k, x));
                 return Some(res.into_iter().collect());
-            } else {
-                res.push(x);
             }
+            res.push(x);
         }
         // reached the end
         self.done = true;
@@ -71,30 +425,281 @@ pub trait IterSplit: Sized + IntoIterator + crate::into_iter_seal::IntoIterSeal
         keep_sep: bool,
     ) -> SplitIter<Self::IntoIter, F, V>;
 
+    #[allow(missing_docs)]
+    fn split_mode_impl<F: FnMut(&Self::Item) -> bool, V: FromIterator<Self::Item>>(
+        self,
+        max_len: Option<usize>,
+        is_sep: F,
+        sep_mode: SepMode,
+    ) -> SplitIter<Self::IntoIter, F, V>;
+
     /// Splits an iterator into smaller chunks.
     /// The separators are the items where `is_sep` returns `true`
     ///
-    /// If `keep_sep` is `true`, the separators will also be emitted (on their own)
+    /// `sep_mode` decides what happens to the separators (see [`SepMode`](enum.SepMode.html)),
+    /// `true` and `false` are short for `SepMode::Separate` and `SepMode::Discard`
     fn split<F: FnMut(&Self::Item) -> bool, V: FromIterator<Self::Item>>(
         self,
         is_sep: F,
-        keep_sep: bool,
+        sep_mode: impl Into<SepMode>,
     ) -> SplitIter<Self::IntoIter, F, V> {
-        self.split_impl(None, is_sep, keep_sep)
+        self.split_mode_impl(None, is_sep, sep_mode.into())
     }
 
     /// Splits an iterator into smaller chunks,
     /// **stopping once it reaches the specified (`n`) number of chunks**.
     /// The separators are the items where `is_sep` returns `true`
     ///
-    /// If `keep_sep` is `true`, the separators will also be emitted (on their own)
+    /// `sep_mode` decides what happens to the separators (see [`SepMode`](enum.SepMode.html)),
+    /// `true` and `false` are short for `SepMode::Separate` and `SepMode::Discard`
     fn splitn<F: FnMut(&Self::Item) -> bool, V: FromIterator<Self::Item>>(
         self,
         n: usize,
         is_sep: F,
-        keep_sep: bool,
+        sep_mode: impl Into<SepMode>,
+    ) -> SplitIter<Self::IntoIter, F, V> {
+        self.split_mode_impl(Some(n), is_sep, sep_mode.into())
+    }
+
+    /// Like [`split`](#method.split), but treats the separators as terminators:
+    /// if the last segment is empty, it is dropped (see [`EmptySegments`](enum.EmptySegments.html))
+    ///
+    /// This is useful for line-oriented records, where the last newline ends a record.
+    ///
+    /// Example:
+    /// ```
+    /// # use tlib::iter_tools::IterSplit;
+    /// let v: Vec<String> = "a\nb\n".chars().split_terminator(|&c| c == '\n', false).collect();
+    /// assert_eq!(v, vec!["a", "b"]);
+    /// ```
+    fn split_terminator<F: FnMut(&Self::Item) -> bool, V: FromIterator<Self::Item>>(
+        self,
+        is_sep: F,
+        sep_mode: impl Into<SepMode>,
     ) -> SplitIter<Self::IntoIter, F, V> {
-        self.split_impl(Some(n), is_sep, keep_sep)
+        self.split(is_sep, sep_mode)
+            .empty_segments(EmptySegments::Terminator)
+    }
+
+    /// Like [`split`](#method.split), but starts splitting from the back
+    ///
+    /// The segments are returned from last to first, but the items in each segment stay in their original order
+    /// (and `sep_mode` also refers to the original order)
+    ///
+    /// Example:
+    /// ```
+    /// # use tlib::iter_tools::IterSplit;
+    /// let v: Vec<String> = "a/b/c".chars().rsplit(|&c| c == '/', false).collect();
+    /// assert_eq!(v, vec!["c", "b", "a"]);
+    /// ```
+    fn rsplit<F: FnMut(&Self::Item) -> bool, V: FromIterator<Self::Item>>(
+        self,
+        is_sep: F,
+        sep_mode: impl Into<SepMode>,
+    ) -> RSplitIter<Self::IntoIter, F, V>
+    where
+        Self::IntoIter: DoubleEndedIterator,
+    {
+        RSplitIter {
+            inner: self
+                .into_iter()
+                .rev()
+                .split_mode_impl(None, is_sep, sep_mode.into().reversed()),
+            drop_empty_first: false,
+            _marker: PhantomData,
+        }
+    }
+
+    /// Like [`splitn`](#method.splitn), but starts splitting from the back
+    ///
+    /// The segments are returned from last to first, but the items in each segment stay in their original order
+    /// (and `sep_mode` also refers to the original order)
+    ///
+    /// Example:
+    /// ```
+    /// # use tlib::iter_tools::IterSplit;
+    /// let v: Vec<String> = "a/b/c".chars().rsplitn(2, |&c| c == '/', false).collect();
+    /// assert_eq!(v, vec!["c", "a/b"]);
+    /// ```
+    fn rsplitn<F: FnMut(&Self::Item) -> bool, V: FromIterator<Self::Item>>(
+        self,
+        n: usize,
+        is_sep: F,
+        sep_mode: impl Into<SepMode>,
+    ) -> RSplitIter<Self::IntoIter, F, V>
+    where
+        Self::IntoIter: DoubleEndedIterator,
+    {
+        RSplitIter {
+            inner: self.into_iter().rev().split_mode_impl(
+                Some(n),
+                is_sep,
+                sep_mode.into().reversed(),
+            ),
+            drop_empty_first: false,
+            _marker: PhantomData,
+        }
+    }
+
+    /// Like [`split`](#method.split), but instead of collecting each segment,
+    /// returns it as an iterator that borrows the underlying one (see [`StreamingSplit`](struct.StreamingSplit.html))
+    ///
+    /// This way, even huge segments can be processed in constant memory.
+    ///
+    /// Example:
+    /// ```
+    /// # use tlib::iter_tools::IterSplit;
+    /// let mut split = (0..30).split_streaming(|&x| x % 10 == 9, false);
+    /// let mut sums = Vec::new();
+    /// while let Some(group) = split.next_group() {
+    ///     sums.push(group.sum::<i32>());
+    /// }
+    /// assert_eq!(sums, vec![36, 126, 216, 0]);
+    /// ```
+    fn split_streaming<F: FnMut(&Self::Item) -> bool>(
+        self,
+        is_sep: F,
+        sep_mode: impl Into<SepMode>,
+    ) -> StreamingSplit<Self::IntoIter, F> {
+        StreamingSplit::new(self.into_iter(), None, is_sep, sep_mode.into())
+    }
+
+    /// Like [`splitn`](#method.splitn), but instead of collecting each segment,
+    /// returns it as an iterator that borrows the underlying one (see [`StreamingSplit`](struct.StreamingSplit.html))
+    fn splitn_streaming<F: FnMut(&Self::Item) -> bool>(
+        self,
+        n: usize,
+        is_sep: F,
+        sep_mode: impl Into<SepMode>,
+    ) -> StreamingSplit<Self::IntoIter, F> {
+        StreamingSplit::new(self.into_iter(), Some(n), is_sep, sep_mode.into())
+    }
+
+    #[allow(missing_docs)]
+    fn split_between_impl<F: FnMut(&Self::Item, &Self::Item) -> bool, V: FromIterator<Self::Item>>(
+        self,
+        max_len: Option<usize>,
+        is_boundary: F,
+    ) -> SplitBetweenIter<Self::IntoIter, F, V>;
+
+    /// Splits an iterator into smaller chunks, between every two consecutive items `a` and `b`
+    /// where `is_boundary(a, b)` returns `true`
+    ///
+    /// Unlike with `split`, no items are separators, so an empty iterator gives no segments at all
+    /// (and no segment is empty).
+    ///
+    /// Example:
+    /// ```
+    /// # use tlib::iter_tools::IterSplit;
+    /// let timestamps = vec![1, 2, 3, 10, 11, 20];
+    /// let bursts: Vec<Vec<_>> = timestamps.split_between(|a, b| b - a > 5).collect();
+    /// assert_eq!(bursts, vec![vec![1, 2, 3], vec![10, 11], vec![20]]);
+    /// ```
+    fn split_between<F: FnMut(&Self::Item, &Self::Item) -> bool, V: FromIterator<Self::Item>>(
+        self,
+        is_boundary: F,
+    ) -> SplitBetweenIter<Self::IntoIter, F, V> {
+        self.split_between_impl(None, is_boundary)
+    }
+
+    /// Like [`split_between`](#method.split_between),
+    /// but **stops once it reaches the specified (`n`) number of chunks**
+    fn splitn_between<F: FnMut(&Self::Item, &Self::Item) -> bool, V: FromIterator<Self::Item>>(
+        self,
+        n: usize,
+        is_boundary: F,
+    ) -> SplitBetweenIter<Self::IntoIter, F, V> {
+        self.split_between_impl(Some(n), is_boundary)
+    }
+
+    #[allow(missing_docs)]
+    fn chunk_by_impl<K: PartialEq, G: FnMut(&Self::Item) -> K, V: FromIterator<Self::Item>>(
+        self,
+        max_len: Option<usize>,
+        key: G,
+    ) -> ChunkByIter<Self::IntoIter, G, K, V>;
+
+    /// Splits an iterator into runs of consecutive items for which `key` returns the same value
+    ///
+    /// This works like [`split_between`](#method.split_between) with a boundary wherever the key changes,
+    /// but `key` is only called once per item.
+    ///
+    /// Example:
+    /// ```
+    /// # use tlib::iter_tools::IterSplit;
+    /// let v: Vec<String> = "aaBBcA".chars().chunk_by(|c| c.is_uppercase()).collect();
+    /// assert_eq!(v, vec!["aa", "BB", "c", "A"]);
+    /// ```
+    fn chunk_by<K: PartialEq, G: FnMut(&Self::Item) -> K, V: FromIterator<Self::Item>>(
+        self,
+        key: G,
+    ) -> ChunkByIter<Self::IntoIter, G, K, V> {
+        self.chunk_by_impl(None, key)
+    }
+
+    /// Like [`chunk_by`](#method.chunk_by),
+    /// but **stops once it reaches the specified (`n`) number of chunks**
+    fn chunkn_by<K: PartialEq, G: FnMut(&Self::Item) -> K, V: FromIterator<Self::Item>>(
+        self,
+        n: usize,
+        key: G,
+    ) -> ChunkByIter<Self::IntoIter, G, K, V> {
+        self.chunk_by_impl(Some(n), key)
+    }
+
+    #[allow(missing_docs)]
+    fn split_seq_impl<V: FromIterator<Self::Item>>(
+        self,
+        max_len: Option<usize>,
+        sep: Vec<Self::Item>,
+        sep_mode: SepMode,
+    ) -> SplitSeqIter<Self::IntoIter, V>;
+
+    /// Splits an iterator into smaller chunks.
+    /// The separators are the (non-overlapping) occurrences of the sequence `sep`
+    ///
+    /// Only `sep.len()` items are buffered to look for the separator.
+    ///
+    /// `sep_mode` decides what happens to the separators (see [`SepMode`](enum.SepMode.html)),
+    /// `true` and `false` are short for `SepMode::Separate` and `SepMode::Discard`
+    ///
+    /// # Panics
+    ///
+    /// Panics if `sep` is empty
+    ///
+    /// Example:
+    /// ```
+    /// # use tlib::iter_tools::IterSplit;
+    /// let v: Vec<String> = "a\r\nb\r\n".chars().split_seq("\r\n".chars(), false).collect();
+    /// assert_eq!(v, vec!["a", "b", ""]);
+    /// ```
+    fn split_seq<V: FromIterator<Self::Item>>(
+        self,
+        sep: impl IntoIterator<Item = Self::Item>,
+        sep_mode: impl Into<SepMode>,
+    ) -> SplitSeqIter<Self::IntoIter, V> {
+        self.split_seq_impl(None, sep.into_iter().collect(), sep_mode.into())
+    }
+
+    /// Splits an iterator into smaller chunks,
+    /// **stopping once it reaches the specified (`n`) number of chunks**.
+    /// The separators are the (non-overlapping) occurrences of the sequence `sep`
+    ///
+    /// Only `sep.len()` items are buffered to look for the separator.
+    ///
+    /// `sep_mode` decides what happens to the separators (see [`SepMode`](enum.SepMode.html)),
+    /// `true` and `false` are short for `SepMode::Separate` and `SepMode::Discard`
+    ///
+    /// # Panics
+    ///
+    /// Panics if `sep` is empty
+    fn splitn_seq<V: FromIterator<Self::Item>>(
+        self,
+        n: usize,
+        sep: impl IntoIterator<Item = Self::Item>,
+        sep_mode: impl Into<SepMode>,
+    ) -> SplitSeqIter<Self::IntoIter, V> {
+        self.split_seq_impl(Some(n), sep.into_iter().collect(), sep_mode.into())
     }
 }
 
@@ -104,14 +709,80 @@ impl<I: IntoIterator> IterSplit for I {
         max_len: Option<usize>,
         is_sep: F,
         keep_sep: bool,
+    ) -> SplitIter<Self::IntoIter, F, V> {
+        self.split_mode_impl(max_len, is_sep, keep_sep.into())
+    }
+
+    fn split_mode_impl<F: FnMut(&Self::Item) -> bool, V: FromIterator<Self::Item>>(
+        self,
+        max_len: Option<usize>,
+        is_sep: F,
+        sep_mode: SepMode,
     ) -> SplitIter<Self::IntoIter, F, V> {
         SplitIter {
             curr_len: 0,
             max_len,
             iter: self.into_iter(),
             is_sep,
-            keep_sep,
+            sep_mode,
+            last_sep: None,
+            empty: EmptySegments::Keep,
+            done: false,
+            _marker: PhantomData,
+        }
+    }
+
+    fn split_between_impl<
+        F: FnMut(&Self::Item, &Self::Item) -> bool,
+        V: FromIterator<Self::Item>,
+    >(
+        self,
+        max_len: Option<usize>,
+        is_boundary: F,
+    ) -> SplitBetweenIter<Self::IntoIter, F, V> {
+        SplitBetweenIter {
+            curr_len: 0,
+            max_len,
+            iter: self.into_iter(),
+            is_boundary,
+            pending: None,
+            done: false,
+            _marker: PhantomData,
+        }
+    }
+
+    fn chunk_by_impl<K: PartialEq, G: FnMut(&Self::Item) -> K, V: FromIterator<Self::Item>>(
+        self,
+        max_len: Option<usize>,
+        key: G,
+    ) -> ChunkByIter<Self::IntoIter, G, K, V> {
+        ChunkByIter {
+            curr_len: 0,
+            max_len,
+            iter: self.into_iter(),
+            key,
+            pending: None,
+            done: false,
+            _marker: PhantomData,
+        }
+    }
+
+    fn split_seq_impl<V: FromIterator<Self::Item>>(
+        self,
+        max_len: Option<usize>,
+        sep: Vec<Self::Item>,
+        sep_mode: SepMode,
+    ) -> SplitSeqIter<Self::IntoIter, V> {
+        assert!(!sep.is_empty(), "tried to split by an empty sequence");
+        SplitSeqIter {
+            curr_len: 0,
+            max_len,
+            iter: self.into_iter(),
+            window: VecDeque::with_capacity(sep.len()),
+            sep,
+            sep_mode,
             last_sep: None,
+            empty: EmptySegments::Keep,
             done: false,
             _marker: PhantomData,
         }
@@ -130,31 +801,181 @@ pub trait SplitNotEscapedString {
         keep_sep: bool,
     ) -> V;
 
+    #[allow(missing_docs)]
+    fn split_not_escaped_mode_impl<V: FromIterator<String>>(
+        &self,
+        max_len: Option<usize>,
+        sep: char,
+        esc: char,
+        sep_mode: SepMode,
+        empty: EmptySegments,
+    ) -> V;
+
     /// Analogous to [`IterSplit::split`](trait.IterSplit#method.split)
     ///
-    /// Splits with `sep`, escapes with `esc`
+    /// Splits with `sep`, escapes with `esc`.
+    /// `sep_mode` works like in `IterSplit`
     #[inline]
     fn split_not_escaped<V: FromIterator<String>>(
         &self,
         sep: char,
         esc: char,
-        keep_sep: bool,
+        sep_mode: impl Into<SepMode>,
     ) -> V {
-        self.split_not_escaped_impl(None, sep, esc, keep_sep)
+        self.split_not_escaped_mode_impl(None, sep, esc, sep_mode.into(), EmptySegments::Keep)
     }
 
     /// Analogous to [`IterSplit::splitn`](trait.IterSplit#method.splitn)
     ///
-    /// Splits with `sep`, escapes with `esc`
+    /// Splits with `sep`, escapes with `esc`.
+    /// `sep_mode` works like in `IterSplit`
     #[inline]
     fn splitn_not_escaped<V: FromIterator<String>>(
         &self,
         n: usize,
         sep: char,
         esc: char,
-        keep_sep: bool,
+        sep_mode: impl Into<SepMode>,
+    ) -> V {
+        self.split_not_escaped_mode_impl(Some(n), sep, esc, sep_mode.into(), EmptySegments::Keep)
+    }
+
+    /// Analogous to [`IterSplit::split_terminator`](trait.IterSplit#method.split_terminator)
+    ///
+    /// Splits with `sep`, escapes with `esc`.
+    /// `sep_mode` works like in `IterSplit`
+    ///
+    /// Example:
+    /// ```
+    /// # use tlib::iter_tools::SplitNotEscapedString;
+    /// let v: Vec<_> = "a;b\\;c;".split_terminator_not_escaped(';', '\\', false);
+    /// assert_eq!(v, vec!["a", "b;c"]);
+    /// ```
+    #[inline]
+    fn split_terminator_not_escaped<V: FromIterator<String>>(
+        &self,
+        sep: char,
+        esc: char,
+        sep_mode: impl Into<SepMode>,
+    ) -> V {
+        self.split_not_escaped_mode_impl(None, sep, esc, sep_mode.into(), EmptySegments::Terminator)
+    }
+
+    /// Like [`split_not_escaped`](#method.split_not_escaped), but drops all empty segments
+    ///
+    /// Splits with `sep`, escapes with `esc`.
+    /// `sep_mode` works like in `IterSplit`
+    #[inline]
+    fn split_not_escaped_skip_empty<V: FromIterator<String>>(
+        &self,
+        sep: char,
+        esc: char,
+        sep_mode: impl Into<SepMode>,
+    ) -> V {
+        self.split_not_escaped_mode_impl(None, sep, esc, sep_mode.into(), EmptySegments::Skip)
+    }
+
+    #[allow(missing_docs)]
+    fn split_not_escaped_cow_impl(
+        &self,
+        max_len: Option<usize>,
+        sep: char,
+        esc: char,
+        sep_mode: SepMode,
+    ) -> SplitNotEscapedCow<'_>;
+
+    /// Like [`split_not_escaped`](#method.split_not_escaped), but lazily returns the segments as `Cow<str>`s
+    /// which borrow from the string unless they need to be unescaped (i.e. contain an escaped separator)
+    ///
+    /// Example:
+    /// ```
+    /// # use tlib::iter_tools::SplitNotEscapedString;
+    /// # use std::borrow::Cow;
+    /// let v: Vec<_> = r"a:b\:c".split_not_escaped_cow(':', '\\', false).collect();
+    /// assert_eq!(v, vec![Cow::Borrowed("a"), Cow::Owned("b:c".to_string())]);
+    /// ```
+    #[inline]
+    fn split_not_escaped_cow(
+        &self,
+        sep: char,
+        esc: char,
+        sep_mode: impl Into<SepMode>,
+    ) -> SplitNotEscapedCow<'_> {
+        self.split_not_escaped_cow_impl(None, sep, esc, sep_mode.into())
+    }
+
+    /// Like [`splitn_not_escaped`](#method.splitn_not_escaped), but lazily returns the segments as `Cow<str>`s
+    /// (see [`split_not_escaped_cow`](#method.split_not_escaped_cow))
+    #[inline]
+    fn splitn_not_escaped_cow(
+        &self,
+        n: usize,
+        sep: char,
+        esc: char,
+        sep_mode: impl Into<SepMode>,
+    ) -> SplitNotEscapedCow<'_> {
+        self.split_not_escaped_cow_impl(Some(n), sep, esc, sep_mode.into())
+    }
+
+    #[allow(missing_docs)]
+    fn rsplit_not_escaped_impl<V: FromIterator<String>>(
+        &self,
+        max_len: Option<usize>,
+        sep: char,
+        esc: char,
+        sep_mode: SepMode,
+        empty: EmptySegments,
+    ) -> V;
+
+    /// Analogous to [`IterSplit::rsplit`](trait.IterSplit#method.rsplit)
+    ///
+    /// Splits with `sep`, escapes with `esc`.
+    /// `sep_mode` works like in `IterSplit`
+    #[inline]
+    fn rsplit_not_escaped<V: FromIterator<String>>(
+        &self,
+        sep: char,
+        esc: char,
+        sep_mode: impl Into<SepMode>,
+    ) -> V {
+        self.rsplit_not_escaped_impl(None, sep, esc, sep_mode.into(), EmptySegments::Keep)
+    }
+
+    /// Like [`rsplit_not_escaped`](#method.rsplit_not_escaped), but treats the separators as terminators
+    /// (like [`split_terminator_not_escaped`](#method.split_terminator_not_escaped))
+    ///
+    /// Splits with `sep`, escapes with `esc`.
+    /// `sep_mode` works like in `IterSplit`
+    #[inline]
+    fn rsplit_terminator_not_escaped<V: FromIterator<String>>(
+        &self,
+        sep: char,
+        esc: char,
+        sep_mode: impl Into<SepMode>,
+    ) -> V {
+        self.rsplit_not_escaped_impl(None, sep, esc, sep_mode.into(), EmptySegments::Terminator)
+    }
+
+    /// Analogous to [`IterSplit::rsplitn`](trait.IterSplit#method.rsplitn)
+    ///
+    /// Splits with `sep`, escapes with `esc`.
+    /// `sep_mode` works like in `IterSplit`
+    ///
+    /// Example:
+    /// ```
+    /// # use tlib::iter_tools::SplitNotEscapedString;
+    /// let v: Vec<_> = r"dir\/sub/file".rsplitn_not_escaped(2, '/', '\\', false);
+    /// assert_eq!(v, vec!["file", "dir/sub"]);
+    /// ```
+    #[inline]
+    fn rsplitn_not_escaped<V: FromIterator<String>>(
+        &self,
+        n: usize,
+        sep: char,
+        esc: char,
+        sep_mode: impl Into<SepMode>,
     ) -> V {
-        self.split_not_escaped_impl(Some(n), sep, esc, keep_sep)
+        self.rsplit_not_escaped_impl(Some(n), sep, esc, sep_mode.into(), EmptySegments::Keep)
     }
 }
 
@@ -165,11 +986,23 @@ impl<S: AsRef<str>> SplitNotEscapedString for S {
         sep: char,
         esc: char,
         keep_sep: bool,
+    ) -> V {
+        self.split_not_escaped_mode_impl(max_len, sep, esc, keep_sep.into(), EmptySegments::Keep)
+    }
+
+    fn split_not_escaped_mode_impl<V: FromIterator<String>>(
+        &self,
+        max_len: Option<usize>,
+        sep: char,
+        esc: char,
+        sep_mode: SepMode,
+        empty: EmptySegments,
     ) -> V {
         self.as_ref()
             .chars()
             .auto_escape(indicator(esc))
-            .split_impl::<_, Vec<_>>(max_len, indicator_not_escaped(sep), keep_sep)
+            .split_mode_impl::<_, Vec<_>>(max_len, indicator_not_escaped(sep), sep_mode)
+            .empty_segments(empty)
             .map(|v| {
                 v.into_iter()
                     .unescape(unescape_all_except(sep, esc))
@@ -177,6 +1010,71 @@ impl<S: AsRef<str>> SplitNotEscapedString for S {
             })
             .collect()
     }
+
+    fn split_not_escaped_cow_impl(
+        &self,
+        max_len: Option<usize>,
+        sep: char,
+        esc: char,
+        sep_mode: SepMode,
+    ) -> SplitNotEscapedCow<'_> {
+        SplitNotEscapedCow::new(self.as_ref(), max_len, sep, esc, sep_mode)
+    }
+
+    fn rsplit_not_escaped_impl<V: FromIterator<String>>(
+        &self,
+        max_len: Option<usize>,
+        sep: char,
+        esc: char,
+        sep_mode: SepMode,
+        empty: EmptySegments,
+    ) -> V {
+        let s = self.as_ref();
+        let unescape = |seg: &str| {
+            seg.chars()
+                .auto_escape(indicator(esc))
+                .unescape(unescape_all_except(sep, esc))
+                .collect::<String>()
+        };
+
+        let mut res = Vec::new();
+        // the end of the current segment
+        let mut end = s.len();
+        let mut count = 0;
+        for (i, c) in s.char_indices().rev() {
+            if max_len == Some(count + 1) {
+                break;
+            }
+            if c != sep {
+                continue;
+            }
+            // a separator is escaped iff it is preceded by an odd number of escape characters
+            // (since each pair of them is an escaped escape character)
+            let n_esc = s[..i].chars().rev().take_while(|&x| x == esc).count();
+            if n_esc % 2 == 0 {
+                let after = i + c.len_utf8();
+                // the start of the current and the end of the next segment
+                let (start, next_end) = match sep_mode {
+                    SepMode::AttachPrev => (after, after),
+                    SepMode::AttachNext => (i, i),
+                    SepMode::Discard | SepMode::Separate => (after, i),
+                };
+                // (the segment at the end of `s` is the last one in the original order)
+                if !empty.drops(start == end, end == s.len()) {
+                    res.push(unescape(&s[start..end]));
+                    count += 1;
+                }
+                if sep_mode == SepMode::Separate {
+                    res.push(c.to_string());
+                }
+                end = next_end;
+            }
+        }
+        if !empty.drops(end == 0, end == s.len()) {
+            res.push(unescape(&s[..end]));
+        }
+        res.into_iter().collect()
+    }
 }
 
 #[cfg(test)]
@@ -230,4 +1128,264 @@ mod tests {
             vec![String::new()]
         );
     }
+
+    #[test]
+    fn split_seq() {
+        let bytes = [1u8, 0xFF, 0x00, 2, 0xFF, 3, 0xFF, 0xFF, 0x00];
+        let v: Vec<Vec<_>> = bytes
+            .iter()
+            .copied()
+            .split_seq([0xFF, 0x00], false)
+            .collect();
+        assert_eq!(v, vec![vec![1], vec![2, 0xFF, 3, 0xFF], vec![]]);
+
+        let v: Vec<String> = "a--b---c".chars().split_seq("--".chars(), true).collect();
+        assert_eq!(v, vec!["a", "--", "b", "--", "-c"]);
+
+        let v: Vec<String> = "a--b--c"
+            .chars()
+            .splitn_seq(2, "--".chars(), false)
+            .collect();
+        assert_eq!(v, vec!["a", "b--c"]);
+
+        let v: Vec<String> = "".chars().split_seq("--".chars(), false).collect();
+        assert_eq!(v, vec![""]);
+        let v: Vec<String> = "a-".chars().split_seq("--".chars(), false).collect();
+        assert_eq!(v, vec!["a-"]);
+    }
+
+    #[test]
+    fn rsplit() {
+        let v: Vec<Vec<_>> = vec![1, 0, 2, 3, 0, 4].rsplit(|&x| x == 0, true).collect();
+        assert_eq!(v, vec![vec![4], vec![0], vec![2, 3], vec![0], vec![1]]);
+        let v: Vec<Vec<_>> = vec![1, 0, 2, 3, 0].rsplitn(2, |&x| x == 0, false).collect();
+        assert_eq!(v, vec![vec![], vec![1, 0, 2, 3]]);
+    }
+
+    #[test]
+    fn rsplit_string() {
+        // the reverse split has to agree with the forward split
+        let modes = [
+            SepMode::Discard,
+            SepMode::Separate,
+            SepMode::AttachPrev,
+            SepMode::AttachNext,
+        ];
+        for s in &[
+            "",
+            "abcd",
+            "a:b:c",
+            r"a\:b:c",
+            r"a\\:b:c",
+            r"a\\\:b:c\",
+            r":\\\\:\:",
+            "ä:ö\\ü:",
+        ] {
+            for &mode in &modes {
+                let mut forward: Vec<String> = s.split_not_escaped(':', '\\', mode);
+                forward.reverse();
+                assert_eq!(s.rsplit_not_escaped::<Vec<_>>(':', '\\', mode), forward);
+            }
+        }
+
+        let s = r"a:b\:c:d\\:e";
+        assert_eq!(
+            s.rsplitn_not_escaped::<Vec<_>>(2, ':', '\\', false),
+            vec!["e".to_string(), r"a:b:c:d\\".to_string()]
+        );
+        assert_eq!(
+            s.rsplitn_not_escaped::<Vec<_>>(3, ':', '\\', false),
+            vec!["e".to_string(), r"d\\".to_string(), r"a:b:c".to_string()]
+        );
+    }
+
+    #[test]
+    fn sep_modes() {
+        let split = |mode| {
+            "a:b::c"
+                .chars()
+                .split(|&c| c == ':', mode)
+                .collect::<Vec<String>>()
+        };
+        assert_eq!(split(SepMode::Discard), vec!["a", "b", "", "c"]);
+        assert_eq!(
+            split(SepMode::Separate),
+            vec!["a", ":", "b", ":", "", ":", "c"]
+        );
+        assert_eq!(split(SepMode::AttachPrev), vec!["a:", "b:", ":", "c"]);
+        assert_eq!(split(SepMode::AttachNext), vec!["a", ":b", ":", ":c"]);
+
+        // the `bool` hooks still work like before
+        let v: Vec<String> = "a:b::c"
+            .chars()
+            .split_impl(None, |&c| c == ':', true)
+            .collect();
+        assert_eq!(v, split(SepMode::Separate));
+
+        let v: Vec<String> = "a:b:c"
+            .chars()
+            .splitn(2, |&c| c == ':', SepMode::AttachNext)
+            .collect();
+        assert_eq!(v, vec!["a", ":b:c"]);
+        let v: Vec<String> = "a:b:c"
+            .chars()
+            .rsplit(|&c| c == ':', SepMode::AttachPrev)
+            .collect();
+        assert_eq!(v, vec!["c", "b:", "a:"]);
+
+        let v: Vec<String> = "a\r\nb\r\n"
+            .chars()
+            .split_seq("\r\n".chars(), SepMode::AttachPrev)
+            .collect();
+        assert_eq!(v, vec!["a\r\n", "b\r\n", ""]);
+        let v: Vec<String> = "a--b"
+            .chars()
+            .split_seq("--".chars(), SepMode::AttachNext)
+            .collect();
+        assert_eq!(v, vec!["a", "--b"]);
+
+        let v: Vec<String> = "a\\:b:c".split_not_escaped(':', '\\', SepMode::AttachPrev);
+        assert_eq!(v, vec!["a:b:", "c"]);
+    }
+
+    #[test]
+    fn empty_segments() {
+        let split = |s: &str, empty| {
+            s.chars()
+                .split(|&c| c == ':', false)
+                .empty_segments(empty)
+                .collect::<Vec<String>>()
+        };
+        assert_eq!(split("a::b:", EmptySegments::Keep), vec!["a", "", "b", ""]);
+        assert_eq!(
+            split("a::b:", EmptySegments::Terminator),
+            vec!["a", "", "b"]
+        );
+        assert_eq!(split("a::b:", EmptySegments::Skip), vec!["a", "b"]);
+        assert_eq!(split("", EmptySegments::Terminator), Vec::<String>::new());
+        assert_eq!(split(":", EmptySegments::Skip), Vec::<String>::new());
+
+        // skipped segments don't count towards the limit
+        let v: Vec<String> = "::a::b:c"
+            .chars()
+            .splitn(2, |&c| c == ':', false)
+            .empty_segments(EmptySegments::Skip)
+            .collect();
+        assert_eq!(v, vec!["a", ":b:c"]);
+
+        let v: Vec<String> = "a\nb\n"
+            .chars()
+            .split_terminator(|&c| c == '\n', SepMode::AttachPrev)
+            .collect();
+        assert_eq!(v, vec!["a\n", "b\n"]);
+        let v: Vec<String> = "a--b----"
+            .chars()
+            .split_seq("--".chars(), false)
+            .empty_segments(EmptySegments::Skip)
+            .collect();
+        assert_eq!(v, vec!["a", "b"]);
+
+        let v: Vec<String> = "a:b:"
+            .chars()
+            .rsplit(|&c| c == ':', false)
+            .empty_segments(EmptySegments::Terminator)
+            .collect();
+        assert_eq!(v, vec!["b", "a"]);
+
+        // the last call wins, regardless of what was set before
+        let v: Vec<String> = "a::b:"
+            .chars()
+            .rsplit(|&c| c == ':', false)
+            .empty_segments(EmptySegments::Skip)
+            .empty_segments(EmptySegments::Terminator)
+            .collect();
+        let mut expected: Vec<&str> = "a::b:".split_terminator(':').collect();
+        expected.reverse();
+        assert_eq!(v, expected);
+        assert_eq!(v, vec!["b", "", "a"]);
+    }
+
+    #[test]
+    fn empty_segments_string() {
+        for s in &["", ":", "a:", ":a", "a::b", r"a\::b\:", r"a\:"] {
+            for &empty in &[
+                EmptySegments::Keep,
+                EmptySegments::Terminator,
+                EmptySegments::Skip,
+            ] {
+                for &mode in &[SepMode::Discard, SepMode::Separate] {
+                    let mut forward: Vec<String> =
+                        s.split_not_escaped_mode_impl(None, ':', '\\', mode, empty);
+                    forward.reverse();
+                    let backward: Vec<String> =
+                        s.rsplit_not_escaped_impl(None, ':', '\\', mode, empty);
+                    assert_eq!(backward, forward, "{:?} {:?} {:?}", s, mode, empty);
+                }
+            }
+        }
+
+        assert_eq!(
+            "a;;b;".split_not_escaped_skip_empty::<Vec<_>>(';', '\\', false),
+            vec!["a".to_string(), "b".to_string()]
+        );
+        assert_eq!(
+            "".split_terminator_not_escaped::<Vec<String>>(';', '\\', false),
+            Vec::<String>::new()
+        );
+        // the original hook keeps all empty segments
+        assert_eq!(
+            "a;;b;".split_not_escaped_impl::<Vec<String>>(None, ';', '\\', false),
+            vec!["a", "", "b", ""]
+        );
+    }
+
+    #[test]
+    fn split_between() {
+        let v: Vec<Vec<_>> = vec![1, 2, 4, 5, 6, 9]
+            .split_between(|a, b| b - a > 1)
+            .collect();
+        assert_eq!(v, vec![vec![1, 2], vec![4, 5, 6], vec![9]]);
+        let v: Vec<Vec<_>> = vec![1, 2, 4, 5, 6, 9]
+            .splitn_between(2, |a, b| b - a > 1)
+            .collect();
+        assert_eq!(v, vec![vec![1, 2], vec![4, 5, 6, 9]]);
+        let v: Vec<Vec<i32>> = Vec::new().split_between(|_, _| true).collect();
+        assert!(v.is_empty());
+
+        let words = [
+            "apple",
+            "avocado",
+            "banana",
+            "blueberry",
+            "cherry",
+            "apricot",
+        ];
+        let v: Vec<Vec<_>> = words.iter().chunk_by(|w| w.chars().next()).collect();
+        assert_eq!(
+            v,
+            vec![
+                vec![&"apple", &"avocado"],
+                vec![&"banana", &"blueberry"],
+                vec![&"cherry"],
+                vec![&"apricot"]
+            ]
+        );
+        let v: Vec<usize> = words
+            .iter()
+            .chunkn_by(2, |w| w.len())
+            .map(|v: Vec<_>| v.len())
+            .collect();
+        assert_eq!(v, vec![1, 5]);
+
+        let mut calls = 0;
+        let v: Vec<Vec<_>> = words
+            .iter()
+            .chunk_by(|w| {
+                calls += 1;
+                w.len()
+            })
+            .collect();
+        assert_eq!(v.len(), 6);
+        assert_eq!(calls, words.len());
+    }
 }
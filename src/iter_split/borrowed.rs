@@ -0,0 +1,357 @@
+// Splitting without copying: the segments borrow from the input
+use super::{EmptySegments, SepMode};
+use crate::auto_escape::{indicator, unescape_all_except, AutoEscape, Unescape};
+use std::borrow::Cow;
+
+/// An iterator for splitting a slice by single items, returning subslices
+pub struct SliceSplitIter<'a, T, F> {
+    slice: &'a [T],
+    // (dyn) the start of the next segment
+    // starts at 0
+    pos: usize,
+    // (dyn) the number of split segments already returned
+    // starts at 0
+    curr_len: usize,
+    max_len: Option<usize>,
+    is_sep: F,
+    // (setting) what to do with the separators
+    sep_mode: SepMode,
+    // (dyn) the index of the separator, if it was kept from the last call to `next`
+    // (for `Separate` and `AttachNext`)
+    // starts at None
+    last_sep: Option<usize>,
+    // (setting) which empty segments to drop
+    empty: EmptySegments,
+    // (dyn) this is set when no more `Some`s should be returned
+    // starts at false
+    done: bool,
+}
+
+impl<'a, T, F> SliceSplitIter<'a, T, F> {
+    /// Sets what to do with empty segments (by default, they are all kept)
+    ///
+    /// Dropped segments don't count towards the limit of `splitn_slice`
+    pub fn empty_segments(mut self, empty: EmptySegments) -> Self {
+        self.empty = empty;
+        self
+    }
+}
+
+impl<'a, T, F: FnMut(&T) -> bool> Iterator for SliceSplitIter<'a, T, F> {
+    type Item = &'a [T];
+
+    fn next(&mut self) -> Option<&'a [T]> {
+        loop {
+            if self.done {
+                return None;
+            }
+            let mut start = self.pos;
+            if let Some(i) = self.last_sep.take() {
+                if self.sep_mode == SepMode::Separate {
+                    return Some(&self.slice[i..=i]);
+                }
+                start = i;
+            }
+            let len = self.slice.len();
+            let found = if self.max_len == Some(self.curr_len + 1) {
+                // the length limit is reached: return the whole rest
+                None
+            } else {
+                self.slice[self.pos..]
+                    .iter()
+                    .position(&mut self.is_sep)
+                    .map(|k| self.pos + k)
+            };
+            let (end, last) = match found {
+                Some(i) => {
+                    self.pos = i + 1;
+                    match self.sep_mode {
+                        SepMode::Discard => (i, false),
+                        SepMode::Separate | SepMode::AttachNext => {
+                            self.last_sep = Some(i);
+                            (i, false)
+                        }
+                        SepMode::AttachPrev => (i + 1, false),
+                    }
+                }
+                None => (len, true),
+            };
+            self.done = last;
+            let seg = &self.slice[start..end];
+            if !self.empty.drops(seg.is_empty(), last) {
+                self.curr_len += 1;
+                return Some(seg);
+            }
+        }
+    }
+}
+
+/// A trait for splitting slices without copying the items
+pub trait SliceSplit<T> {
+    #[allow(missing_docs)]
+    fn split_slice_impl<F: FnMut(&T) -> bool>(
+        &self,
+        max_len: Option<usize>,
+        is_sep: F,
+        sep_mode: SepMode,
+    ) -> SliceSplitIter<'_, T, F>;
+
+    /// Like [`IterSplit::split`](trait.IterSplit#method.split), but the segments are subslices
+    ///
+    /// Example:
+    /// ```
+    /// # use tlib::iter_tools::{SliceSplit, SepMode};
+    /// let v: Vec<&[u8]> = b"GET / HTTP\r\nHost: x\r\n"
+    ///     .split_slice(|&b| b == b'\n', SepMode::AttachPrev)
+    ///     .collect();
+    /// assert_eq!(v, vec![&b"GET / HTTP\r\n"[..], b"Host: x\r\n", b""]);
+    /// ```
+    fn split_slice<F: FnMut(&T) -> bool>(
+        &self,
+        is_sep: F,
+        sep_mode: impl Into<SepMode>,
+    ) -> SliceSplitIter<'_, T, F> {
+        self.split_slice_impl(None, is_sep, sep_mode.into())
+    }
+
+    /// Like [`IterSplit::splitn`](trait.IterSplit#method.splitn), but the segments are subslices
+    fn splitn_slice<F: FnMut(&T) -> bool>(
+        &self,
+        n: usize,
+        is_sep: F,
+        sep_mode: impl Into<SepMode>,
+    ) -> SliceSplitIter<'_, T, F> {
+        self.split_slice_impl(Some(n), is_sep, sep_mode.into())
+    }
+}
+
+impl<T> SliceSplit<T> for [T] {
+    fn split_slice_impl<F: FnMut(&T) -> bool>(
+        &self,
+        max_len: Option<usize>,
+        is_sep: F,
+        sep_mode: SepMode,
+    ) -> SliceSplitIter<'_, T, F> {
+        SliceSplitIter {
+            slice: self,
+            pos: 0,
+            curr_len: 0,
+            max_len,
+            is_sep,
+            sep_mode,
+            last_sep: None,
+            empty: EmptySegments::Keep,
+            done: false,
+        }
+    }
+}
+
+/// An iterator for splitting a string by a single delimeter, respecting escaping,
+/// that only allocates for segments that need to be unescaped
+///
+/// Created by [`SplitNotEscapedString::split_not_escaped_cow`](trait.SplitNotEscapedString.html#method.split_not_escaped_cow)
+pub struct SplitNotEscapedCow<'a> {
+    s: &'a str,
+    sep: char,
+    esc: char,
+    // (dyn) the start of the next segment (in bytes)
+    // starts at 0
+    pos: usize,
+    // (dyn) the number of split segments already returned
+    // starts at 0
+    curr_len: usize,
+    max_len: Option<usize>,
+    // (setting) what to do with the separators
+    sep_mode: SepMode,
+    // (dyn) the index of the separator, if it was kept from the last call to `next`
+    // (for `Separate` and `AttachNext`)
+    // starts at None
+    last_sep: Option<usize>,
+    // (setting) which empty segments to drop
+    empty: EmptySegments,
+    // (dyn) this is set when no more `Some`s should be returned
+    // starts at false
+    done: bool,
+}
+
+impl<'a> SplitNotEscapedCow<'a> {
+    pub(super) fn new(
+        s: &'a str,
+        max_len: Option<usize>,
+        sep: char,
+        esc: char,
+        sep_mode: SepMode,
+    ) -> Self {
+        Self {
+            s,
+            sep,
+            esc,
+            pos: 0,
+            curr_len: 0,
+            max_len,
+            sep_mode,
+            last_sep: None,
+            empty: EmptySegments::Keep,
+            done: false,
+        }
+    }
+
+    /// Sets what to do with empty segments (by default, they are all kept)
+    ///
+    /// Dropped segments don't count towards the limit of `splitn_not_escaped_cow`
+    pub fn empty_segments(mut self, empty: EmptySegments) -> Self {
+        self.empty = empty;
+        self
+    }
+
+    // the byte index of the next separator that isn't escaped
+    fn find_sep(&self) -> Option<usize> {
+        let mut chars = self.s[self.pos..].char_indices();
+        while let Some((i, c)) = chars.next() {
+            if c == self.esc {
+                // the next char is escaped (if there is one)
+                chars.next();
+            } else if c == self.sep {
+                return Some(self.pos + i);
+            }
+        }
+        None
+    }
+
+    // unescaping only changes escaped separators (all other escapes are kept),
+    // so segments without them can be borrowed
+    fn unescape(&self, seg: &'a str) -> Cow<'a, str> {
+        let mut chars = seg.chars();
+        let mut has_escaped_sep = false;
+        while let Some(c) = chars.next() {
+            if c == self.esc && chars.next() == Some(self.sep) {
+                has_escaped_sep = true;
+                break;
+            }
+        }
+        if has_escaped_sep {
+            Cow::Owned(
+                seg.chars()
+                    .auto_escape(indicator(self.esc))
+                    .unescape(unescape_all_except(self.sep, self.esc))
+                    .collect(),
+            )
+        } else {
+            Cow::Borrowed(seg)
+        }
+    }
+}
+
+impl<'a> Iterator for SplitNotEscapedCow<'a> {
+    type Item = Cow<'a, str>;
+
+    fn next(&mut self) -> Option<Cow<'a, str>> {
+        let sep_len = self.sep.len_utf8();
+        loop {
+            if self.done {
+                return None;
+            }
+            let mut start = self.pos;
+            if let Some(i) = self.last_sep.take() {
+                if self.sep_mode == SepMode::Separate {
+                    return Some(Cow::Borrowed(&self.s[i..i + sep_len]));
+                }
+                start = i;
+            }
+            let found = if self.max_len == Some(self.curr_len + 1) {
+                // the length limit is reached: return the whole rest
+                None
+            } else {
+                self.find_sep()
+            };
+            let (end, last) = match found {
+                Some(i) => {
+                    self.pos = i + sep_len;
+                    match self.sep_mode {
+                        SepMode::Discard => (i, false),
+                        SepMode::Separate | SepMode::AttachNext => {
+                            self.last_sep = Some(i);
+                            (i, false)
+                        }
+                        SepMode::AttachPrev => (i + sep_len, false),
+                    }
+                }
+                None => (self.s.len(), true),
+            };
+            self.done = last;
+            let seg = &self.s[start..end];
+            if !self.empty.drops(seg.is_empty(), last) {
+                self.curr_len += 1;
+                return Some(self.unescape(seg));
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::*;
+    use std::borrow::Cow;
+
+    #[test]
+    fn slices() {
+        let v = [1, 0, 2, 3, 0, 0];
+        let segs: Vec<_> = v.split_slice(|&x| x == 0, false).collect();
+        assert_eq!(segs, vec![&[1][..], &[2, 3], &[], &[]]);
+        let segs: Vec<_> = v.split_slice(|&x| x == 0, SepMode::AttachNext).collect();
+        assert_eq!(segs, vec![&[1][..], &[0, 2, 3], &[0], &[0]]);
+        let segs: Vec<_> = v
+            .split_slice(|&x| x == 0, SepMode::Separate)
+            .empty_segments(EmptySegments::Skip)
+            .collect();
+        assert_eq!(segs, vec![&[1][..], &[0], &[2, 3], &[0], &[0]]);
+        let segs: Vec<_> = v.splitn_slice(2, |&x| x == 0, false).collect();
+        assert_eq!(segs, vec![&[1][..], &[2, 3, 0, 0]]);
+
+        // the same as splitting the iterator
+        for &mode in &[
+            SepMode::Discard,
+            SepMode::Separate,
+            SepMode::AttachPrev,
+            SepMode::AttachNext,
+        ] {
+            let by_slice: Vec<Vec<_>> = v
+                .split_slice(|&x| x == 0, mode)
+                .map(<[_]>::to_vec)
+                .collect();
+            let by_iter: Vec<Vec<_>> = v.iter().copied().split(|&x| x == 0, mode).collect();
+            assert_eq!(by_slice, by_iter);
+        }
+    }
+
+    #[test]
+    fn cow_strings() {
+        let segs: Vec<_> = r"a:b\:c:d\e"
+            .split_not_escaped_cow(':', '\\', false)
+            .collect();
+        assert_eq!(segs, vec!["a", "b:c", r"d\e"]);
+        assert!(matches!(segs[0], Cow::Borrowed(_)));
+        assert!(matches!(segs[1], Cow::Owned(_)));
+        assert!(matches!(segs[2], Cow::Borrowed(_)));
+
+        // the same as `split_not_escaped`
+        for s in &["", ":", r"a\\:b:c\:", r"\::ä\ö:", r"a\"] {
+            for &mode in &[
+                SepMode::Discard,
+                SepMode::Separate,
+                SepMode::AttachPrev,
+                SepMode::AttachNext,
+            ] {
+                for n in [None, Some(2)] {
+                    let cow: Vec<String> = s
+                        .split_not_escaped_cow_impl(n, ':', '\\', mode)
+                        .map(Cow::into_owned)
+                        .collect();
+                    let owned: Vec<String> =
+                        s.split_not_escaped_mode_impl(n, ':', '\\', mode, EmptySegments::Keep);
+                    assert_eq!(cow, owned, "{:?} {:?} {:?}", s, mode, n);
+                }
+            }
+        }
+    }
+}
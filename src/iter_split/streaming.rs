@@ -0,0 +1,169 @@
+// Splitting without buffering the segments: each segment is an iterator itself
+use super::SepMode;
+
+/// A "lending iterator" for splitting another iterator by single items,
+/// where each segment is itself an iterator that borrows the `StreamingSplit`
+///
+/// This uses constant memory, no matter how long the segments are.
+/// Since each segment borrows the `StreamingSplit`, this can't implement `Iterator`;
+/// use [`next_group`](#method.next_group) instead (e.g. with `while let`).
+pub struct StreamingSplit<I: Iterator, F> {
+    // (dyn) the number of split segments already returned
+    // starts at 0
+    curr_len: usize,
+    max_len: Option<usize>,
+    iter: I,
+    is_sep: F,
+    // (setting) what to do with the separators
+    sep_mode: SepMode,
+    // (dyn) the separator, if it was kept from the end of the last segment
+    // (for `Separate` and `AttachNext`)
+    // starts at None
+    last_sep: Option<I::Item>,
+    // (dyn) an item to return first in the current segment
+    // starts at None
+    pending: Option<I::Item>,
+    // (dyn) whether the current segment is just `pending` (a separator)
+    // starts at false
+    sep_only: bool,
+    // (dyn) whether the current segment contains the whole rest (because of `max_len`)
+    // starts at false
+    whole_rest: bool,
+    // (dyn) whether the current segment has ended
+    // starts at true (there is no current segment yet)
+    group_end: bool,
+    // (dyn) this is set when the underlying iterator is exhausted
+    // starts at false
+    done: bool,
+}
+
+impl<I: Iterator, F: FnMut(&I::Item) -> bool> StreamingSplit<I, F> {
+    pub(super) fn new(iter: I, max_len: Option<usize>, is_sep: F, sep_mode: SepMode) -> Self {
+        Self {
+            curr_len: 0,
+            max_len,
+            iter,
+            is_sep,
+            sep_mode,
+            last_sep: None,
+            pending: None,
+            sep_only: false,
+            whole_rest: false,
+            group_end: true,
+            done: false,
+        }
+    }
+
+    /// Returns the next segment, or `None` if there are no more
+    ///
+    /// If the previous segment wasn't consumed completely, the rest of it is skipped.
+    pub fn next_group(&mut self) -> Option<SplitGroup<'_, I, F>> {
+        // skip the rest of the previous segment
+        while self.next_in_group().is_some() {}
+        if self.done {
+            return None;
+        }
+        self.group_end = false;
+        self.sep_only = false;
+        if let Some(sep) = self.last_sep.take() {
+            self.pending = Some(sep);
+            if self.sep_mode == SepMode::Separate {
+                self.sep_only = true;
+                return Some(SplitGroup { split: self });
+            }
+        }
+        self.curr_len += 1;
+        self.whole_rest = self.max_len == Some(self.curr_len);
+        Some(SplitGroup { split: self })
+    }
+
+    fn next_in_group(&mut self) -> Option<I::Item> {
+        if self.group_end {
+            return None;
+        }
+        if let Some(x) = self.pending.take() {
+            self.group_end = self.sep_only;
+            return Some(x);
+        }
+        match self.iter.next() {
+            None => {
+                self.group_end = true;
+                self.done = true;
+                None
+            }
+            Some(x) if !self.whole_rest && (self.is_sep)(&x) => {
+                // after a sep, there has to be another segment (even if empty)
+                self.group_end = true;
+                match self.sep_mode {
+                    SepMode::Discard => None,
+                    SepMode::Separate | SepMode::AttachNext => {
+                        self.last_sep = Some(x);
+                        None
+                    }
+                    SepMode::AttachPrev => Some(x),
+                }
+            }
+            Some(x) => Some(x),
+        }
+    }
+}
+
+/// One segment of a [`StreamingSplit`](struct.StreamingSplit.html)
+pub struct SplitGroup<'a, I: Iterator, F> {
+    split: &'a mut StreamingSplit<I, F>,
+}
+
+impl<'a, I: Iterator, F: FnMut(&I::Item) -> bool> Iterator for SplitGroup<'a, I, F> {
+    type Item = I::Item;
+
+    fn next(&mut self) -> Option<I::Item> {
+        self.split.next_in_group()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::*;
+
+    fn collect_groups<I: Iterator<Item = char>, F: FnMut(&char) -> bool>(
+        mut split: StreamingSplit<I, F>,
+    ) -> Vec<String> {
+        let mut res = Vec::new();
+        while let Some(group) = split.next_group() {
+            res.push(group.collect());
+        }
+        res
+    }
+
+    #[test]
+    fn same_as_split() {
+        for s in &["", ":", "a:b::c:", "abc"] {
+            for &mode in &[
+                SepMode::Discard,
+                SepMode::Separate,
+                SepMode::AttachPrev,
+                SepMode::AttachNext,
+            ] {
+                let streamed = collect_groups(s.chars().split_streaming(|&c| c == ':', mode));
+                let buffered: Vec<String> = s.chars().split(|&c| c == ':', mode).collect();
+                assert_eq!(streamed, buffered, "{:?} {:?}", s, mode);
+
+                let streamed = collect_groups(s.chars().splitn_streaming(2, |&c| c == ':', mode));
+                let buffered: Vec<String> = s.chars().splitn(2, |&c| c == ':', mode).collect();
+                assert_eq!(streamed, buffered, "{:?} {:?}", s, mode);
+            }
+        }
+    }
+
+    #[test]
+    fn partially_consumed() {
+        let mut split = (0..).split_streaming(|&x| x % 1000 == 999, false);
+        let mut firsts = Vec::new();
+        for _ in 0..3 {
+            let mut group = split.next_group().unwrap();
+            firsts.push(group.next().unwrap());
+        }
+        assert_eq!(firsts, vec![0, 1000, 2000]);
+        assert_eq!(split.next_group().unwrap().count(), 999);
+    }
+}
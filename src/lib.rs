@@ -2,21 +2,27 @@
 //!
 //! **Crate Features:**
 //!
-//! - `use_std` disables no_std compatibility, adding the `vec_zip` module and the `hashmap` and `hashset` macros
+//! - `use_std` disables no_std compatibility, adding the `vec_zip` module and the `hashmap`, `hashset`, `zip` and `unzip` macros
 //! - `pow` enables the `pow` module
+//! - `derive` enables the `SoA` derive macro
+//! - `rayon` adds parallel versions of the zip and unzip functions to `vec_zip` (together with `use_std`)
 
 #![cfg_attr(not(feature = "use_std"), no_std)]
 #![warn(missing_docs)]
 
 pub use self::linear::{lerp, MapRange};
 pub use self::singleton::Singleton;
+#[cfg(feature = "derive")]
+pub use tlib_derive::SoA;
 
 mod auto_escape;
 #[cfg(feature = "use_std")]
 mod iter_split;
 mod linear;
 mod macros;
+mod multizip;
 mod singleton;
+mod tuple;
 
 mod into_iter_seal {
     pub trait IntoIterSeal {}
@@ -31,6 +37,12 @@ pub mod cargo_tools;
 #[cfg(feature = "pow")]
 pub mod pow;
 
+/// Functions for converting between tuples of arrays and arrays of tuples
+///
+/// These are the fixed-size counterparts of the functions in `vec_zip`.
+/// They don't allocate, so they are also available without `use_std`.
+pub mod array_zip;
+
 /// Useful Functions for converting between tuples of `Vec`s and `Vec`s of tuples
 #[cfg(feature = "use_std")]
 pub mod vec_zip;
@@ -42,4 +54,10 @@ pub mod iter_tools {
     pub use crate::auto_escape::*;
     #[cfg(feature = "use_std")]
     pub use crate::iter_split::*;
+    pub use crate::multizip::*;
+}
+
+/// Some tools for working with tuples
+pub mod tuple_tools {
+    pub use crate::tuple::*;
 }
@@ -114,6 +114,69 @@ macro_rules! hashset {
      };
 }
 
+/// Like the `zip*` functions in [`vec_zip`](vec_zip/index.html), but for any number (at least two) of `Vec`s
+///
+/// Example:
+/// ```
+/// # use tlib::zip;
+/// let v = zip!(vec![1, 2], vec!['a', 'b'], vec!["x", "y"]);
+/// assert_eq!(v, vec![(1, 'a', "x"), (2, 'b', "y")]);
+/// ```
+#[cfg(feature = "use_std")]
+#[macro_export]
+macro_rules! zip {
+    // every recursion step introduces its own (hygienically distinct) `x` as a binding
+    (@acc [$($b:ident)*] [$($done:expr),*] $head:expr $(, $tail:expr)*) => {
+        $crate::zip!(@acc [$($b)* x] [$($done,)* $head] $($tail),*)
+    };
+    (@acc [$($b:ident)*] [$($done:expr),*]) => {
+        $crate::zip_rec!($($done),*)
+            .map($crate::flatten_tuple_fn!($($b),*))
+            .collect::<::std::vec::Vec<_>>()
+    };
+    ($a:expr, $($rest:expr),+ $(,)?) => {
+        $crate::zip!(@acc [] [] $a, $($rest),+)
+    };
+}
+
+/// Like the `unzip*` functions in [`vec_zip`](vec_zip/index.html), but for tuples of any size (at least two)
+///
+/// The columns are counted by the `_`s after the `=>`, one for each tuple element
+///
+/// Example:
+/// ```
+/// # use tlib::unzip;
+/// let v = vec![(1, 'a', "x"), (2, 'b', "y")];
+/// let (a, b, c) = unzip!(v => _, _, _);
+/// assert_eq!(a, vec![1, 2]);
+/// assert_eq!(b, vec!['a', 'b']);
+/// assert_eq!(c, vec!["x", "y"]);
+/// ```
+#[cfg(feature = "use_std")]
+#[macro_export]
+macro_rules! unzip {
+    (@ty $a:ident, $b:ident) => {
+        (::std::vec::Vec<_>, ::std::vec::Vec<_>)
+    };
+    (@ty $head:ident $(, $tail:ident)*) => {
+        (::std::vec::Vec<_>, $crate::unzip!(@ty $($tail),*))
+    };
+    // like in `zip!`, every recursion step introduces its own (hygienically distinct) `x` as a binding,
+    // one for each `_` left in the counter
+    (@acc $v:expr; [$($b:ident)*] [_ $($count:tt)*]) => {
+        $crate::unzip!(@acc $v; [$($b)* x] [$($count)*])
+    };
+    (@acc $v:expr; [$($b:ident)*] []) => {{
+        let nested: $crate::unzip!(@ty $($b),*) = ::std::iter::Iterator::unzip(
+            ::std::iter::IntoIterator::into_iter($v).map($crate::nest_tuple_fn!($($b),*)),
+        );
+        ($crate::flatten_tuple_fn!($($b),*))(nested)
+    }};
+    ($v:expr => $first:tt, $($col:tt),+ $(,)?) => {
+        $crate::unzip!(@acc $v; [] [$first $($col)+])
+    };
+}
+
 /// A macro to print pretty looking and informative debug logs.
 ///
 /// ## Difference from `dbg!`
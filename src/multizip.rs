@@ -0,0 +1,153 @@
+#[cfg(not(feature = "use_std"))]
+use core as std;
+use std::iter::FusedIterator;
+
+/// An iterator that zips any number of iterators at once, yielding flat tuples
+///
+/// Created by the [`multizip`](fn.multizip.html) function
+///
+/// Like [`Iterator::zip`](https://doc.rust-lang.org/std/iter/trait.Iterator.html#method.zip),
+/// this stops as soon as one of the iterators runs out.
+///
+/// It implements `ExactSizeIterator` if all iterators do,
+/// and `DoubleEndedIterator` if all iterators implement both `DoubleEndedIterator` and `ExactSizeIterator`
+/// (the latter is needed to know how many items to skip at the back of the longer iterators)
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub struct MultiZip<T> {
+    iters: T,
+}
+
+/// A tuple of `IntoIterator`s that can be turned into a [`MultiZip`](struct.MultiZip.html)
+///
+/// This is implemented for tuples of up to 12 elements
+pub trait IntoMultiZip {
+    /// The tuple of iterators
+    type Iters;
+
+    #[allow(missing_docs)]
+    fn into_multizip(self) -> MultiZip<Self::Iters>;
+}
+
+/// Zips all the `IntoIterator`s in the tuple `t`, lazily yielding flat tuples
+///
+/// Example:
+/// ```
+/// # use tlib::iter_tools::multizip;
+/// let mut iter = multizip((0..3, "abcd".chars(), [true, false, true].iter()));
+/// assert_eq!(iter.next(), Some((0, 'a', &true)));
+/// assert_eq!(iter.next(), Some((1, 'b', &false)));
+/// assert_eq!(iter.next(), Some((2, 'c', &true)));
+/// assert_eq!(iter.next(), None);
+/// ```
+pub fn multizip<T: IntoMultiZip>(t: T) -> MultiZip<T::Iters> {
+    t.into_multizip()
+}
+
+// combines the upper bounds of two size hints for zipping
+fn min_upper(a: Option<usize>, b: Option<usize>) -> Option<usize> {
+    match (a, b) {
+        (Some(a), Some(b)) => Some(a.min(b)),
+        (a, None) => a,
+        (None, b) => b,
+    }
+}
+
+macro_rules! impl_multizip {
+    ($($t:ident @ $idx:tt),+) => {
+        impl<$($t: IntoIterator),+> IntoMultiZip for ($($t,)+) {
+            type Iters = ($($t::IntoIter,)+);
+
+            fn into_multizip(self) -> MultiZip<Self::Iters> {
+                MultiZip {
+                    iters: ($(self.$idx.into_iter(),)+),
+                }
+            }
+        }
+
+        impl<$($t: Iterator),+> Iterator for MultiZip<($($t,)+)> {
+            type Item = ($($t::Item,)+);
+
+            fn next(&mut self) -> Option<Self::Item> {
+                Some(($(self.iters.$idx.next()?,)+))
+            }
+
+            fn size_hint(&self) -> (usize, Option<usize>) {
+                let mut lower = usize::MAX;
+                let mut upper = None;
+                $(
+                    let (l, u) = self.iters.$idx.size_hint();
+                    lower = lower.min(l);
+                    upper = min_upper(upper, u);
+                )+
+                (lower, upper)
+            }
+        }
+
+        impl<$($t: ExactSizeIterator),+> ExactSizeIterator for MultiZip<($($t,)+)> {}
+
+        impl<$($t: FusedIterator),+> FusedIterator for MultiZip<($($t,)+)> {}
+
+        impl<$($t: DoubleEndedIterator + ExactSizeIterator),+> DoubleEndedIterator
+            for MultiZip<($($t,)+)>
+        {
+            fn next_back(&mut self) -> Option<Self::Item> {
+                let len = self.len();
+                // trim all the iterators to the same length first
+                $(
+                    for _ in len..self.iters.$idx.len() {
+                        self.iters.$idx.next_back();
+                    }
+                )+
+                Some(($(self.iters.$idx.next_back()?,)+))
+            }
+        }
+    };
+}
+
+impl_multizip!(A @ 0);
+impl_multizip!(A @ 0, B @ 1);
+impl_multizip!(A @ 0, B @ 1, C @ 2);
+impl_multizip!(A @ 0, B @ 1, C @ 2, D @ 3);
+impl_multizip!(A @ 0, B @ 1, C @ 2, D @ 3, E @ 4);
+impl_multizip!(A @ 0, B @ 1, C @ 2, D @ 3, E @ 4, F @ 5);
+impl_multizip!(A @ 0, B @ 1, C @ 2, D @ 3, E @ 4, F @ 5, G @ 6);
+impl_multizip!(A @ 0, B @ 1, C @ 2, D @ 3, E @ 4, F @ 5, G @ 6, H @ 7);
+impl_multizip!(A @ 0, B @ 1, C @ 2, D @ 3, E @ 4, F @ 5, G @ 6, H @ 7, I @ 8);
+impl_multizip!(A @ 0, B @ 1, C @ 2, D @ 3, E @ 4, F @ 5, G @ 6, H @ 7, I @ 8, J @ 9);
+impl_multizip!(A @ 0, B @ 1, C @ 2, D @ 3, E @ 4, F @ 5, G @ 6, H @ 7, I @ 8, J @ 9, K @ 10);
+impl_multizip!(A @ 0, B @ 1, C @ 2, D @ 3, E @ 4, F @ 5, G @ 6, H @ 7, I @ 8, J @ 9, K @ 10, L @ 11);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn size_hint() {
+        let iter = multizip((0..5, 0..3, 0..));
+        assert_eq!(iter.size_hint(), (3, Some(3)));
+
+        let iter = multizip((0..5, 0..3, 2..6));
+        assert_eq!(iter.len(), 3);
+
+        let iter = multizip((0.., (0..10).filter(|x| x % 2 == 0)));
+        assert_eq!(iter.size_hint(), (0, Some(10)));
+
+        let iter = multizip((0.., 1..));
+        assert_eq!(iter.size_hint(), (usize::MAX, None));
+    }
+
+    #[test]
+    fn double_ended() {
+        let iter = multizip((0..5, "abc".bytes(), [1.0, 2.0, 3.0, 4.0].iter().copied()));
+        let expected = [(2, b'c', 3.0), (1, b'b', 2.0), (0, b'a', 1.0)];
+        assert!(iter.rev().eq(expected.iter().copied()));
+
+        let mut iter = multizip((0..4, 10..20));
+        assert_eq!(iter.next_back(), Some((3, 13)));
+        assert_eq!(iter.next(), Some((0, 10)));
+        assert_eq!(iter.next_back(), Some((2, 12)));
+        assert_eq!(iter.next(), Some((1, 11)));
+        assert_eq!(iter.next(), None);
+        assert_eq!(iter.next_back(), None);
+    }
+}
@@ -49,6 +49,7 @@ impl<T> DerefMut for Singleton<T> {
 
 impl<T> Singleton<T> {
     /// Creates a new, uninitialized singleton
+    #[allow(clippy::new_without_default)]
     pub const fn new() -> Self {
         Self { inner: None }
     }
@@ -158,12 +159,8 @@ impl<T> Singleton<T> {
     }
 
     /// works like [`map`](#method.map) but modifies `self` instead of returning a new `Singleton`.
-    pub fn map_in_place<F: FnMut(T) -> T>(&mut self, mut f: F) {
-        let inner = match self.inner.take() {
-            Some(t) => Some(f(t)),
-            None => None,
-        };
-        self.inner = inner;
+    pub fn map_in_place<F: FnMut(T) -> T>(&mut self, f: F) {
+        self.inner = self.inner.take().map(f);
     }
 }
 
@@ -0,0 +1,256 @@
+// Macros for converting between flat and nested tuples
+// (these are exported because the `zip!` and `unzip!` macros need them)
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __flatten1 {
+    ($first:ident; $($bind:ident),*) => {
+        |($first, ($($bind),*))| ($first, $($bind),*)
+    };
+}
+
+// turns `(a, (b, (c, d)))` into `(a, b, c, d)`
+#[doc(hidden)]
+#[macro_export]
+macro_rules! flatten_tuple_fn {
+    ($a:ident, $b:ident) => {
+        |($a, $b)| ($a, $b)
+    };
+    ($a:ident, $b:ident, $c:ident) => {
+        $crate::__flatten1!($a; $b, $c)
+    };
+    ($first:ident $(,$bind:ident)*) => {
+        |(x, tup)| {
+            let f1 = $crate::flatten_tuple_fn!($($bind),*);
+            let flat = f1(tup);
+            let f2 = $crate::__flatten1!($first; $($bind),*);
+            f2((x, flat))
+        }
+    }
+}
+
+// the inverse of `flatten_tuple_fn`, turning `(a, b, c)` into `(a, (b, c))`
+#[doc(hidden)]
+#[macro_export]
+macro_rules! nest_tuple_fn {
+    ($($bind:ident),*) => {
+        |($($bind),*)| $crate::nest_tuple_fn!(@nest $($bind),*)
+    };
+    (@nest $a:ident, $b:ident) => {
+        ($a, $b)
+    };
+    (@nest $head:ident $(, $tail:ident)*) => {
+        ($head, $crate::nest_tuple_fn!(@nest $($tail),*))
+    };
+}
+
+// the type of the nested tuple, e.g. `(A, (B, C))` for `A, B, C`
+macro_rules! nested_ty {
+    ($a:ident, $b:ident) => {
+        ($a, $b)
+    };
+    ($head:ident $(, $tail:ident)*) => {
+        ($head, nested_ty!($($tail),*))
+    };
+}
+
+/// Converts a flat tuple like `(a, b, c, d)` into a nested one like `(a, (b, (c, d)))`
+///
+/// This is implemented for tuples with 2 to 12 elements
+///
+/// Example:
+/// ```
+/// # use tlib::tuple_tools::Nest;
+/// assert_eq!((1, 'a', "b", 2.0).nest(), (1, ('a', ("b", 2.0))));
+/// ```
+pub trait Nest {
+    /// The nested tuple
+    type Nested;
+
+    #[allow(missing_docs)]
+    fn nest(self) -> Self::Nested;
+}
+
+/// Converts a nested tuple like `(a, (b, (c, d)))` into a flat one like `(a, b, c, d)`
+///
+/// Since a nested tuple can be flattened to different depths
+/// (`(a, (b, (c, d)))` could also become `(a, b, (c, d))`),
+/// the flat tuple type `T` has to be known (similar to `Into`).
+///
+/// This is implemented for tuples with 2 to 12 elements
+///
+/// Example:
+/// ```
+/// # use tlib::tuple_tools::Flatten;
+/// let flat: (_, _, _, _) = (1, ('a', ("b", 2.0))).flatten();
+/// assert_eq!(flat, (1, 'a', "b", 2.0));
+/// let flat: (_, _, _) = (1, ('a', ("b", 2.0))).flatten();
+/// assert_eq!(flat, (1, 'a', ("b", 2.0)));
+/// ```
+pub trait Flatten<T> {
+    #[allow(missing_docs)]
+    fn flatten(self) -> T;
+}
+
+macro_rules! impl_nest_flatten {
+    ($($t:ident @ $bind:ident),+) => {
+        impl<$($t),+> Nest for ($($t),+) {
+            type Nested = nested_ty!($($t),+);
+
+            fn nest(self) -> Self::Nested {
+                ($crate::nest_tuple_fn!($($bind),+))(self)
+            }
+        }
+
+        impl<$($t),+> Flatten<($($t),+)> for nested_ty!($($t),+) {
+            fn flatten(self) -> ($($t),+) {
+                ($crate::flatten_tuple_fn!($($bind),+))(self)
+            }
+        }
+    };
+}
+
+impl_nest_flatten!(A @ a, B @ b);
+impl_nest_flatten!(A @ a, B @ b, C @ c);
+impl_nest_flatten!(A @ a, B @ b, C @ c, D @ d);
+impl_nest_flatten!(A @ a, B @ b, C @ c, D @ d, E @ e);
+impl_nest_flatten!(A @ a, B @ b, C @ c, D @ d, E @ e, F @ f);
+impl_nest_flatten!(A @ a, B @ b, C @ c, D @ d, E @ e, F @ f, G @ g);
+impl_nest_flatten!(A @ a, B @ b, C @ c, D @ d, E @ e, F @ f, G @ g, H @ h);
+impl_nest_flatten!(A @ a, B @ b, C @ c, D @ d, E @ e, F @ f, G @ g, H @ h, I @ i);
+impl_nest_flatten!(A @ a, B @ b, C @ c, D @ d, E @ e, F @ f, G @ g, H @ h, I @ i, J @ j);
+impl_nest_flatten!(A @ a, B @ b, C @ c, D @ d, E @ e, F @ f, G @ g, H @ h, I @ i, J @ j, K @ k);
+impl_nest_flatten!(A @ a, B @ b, C @ c, D @ d, E @ e, F @ f, G @ g, H @ h, I @ i, J @ j, K @ k, L @ l);
+
+/// A tuple where all elements have the same type, like `(T, T, T)`
+///
+/// This is implemented for tuples with 1 to 12 elements
+///
+/// Example:
+/// ```
+/// # use tlib::tuple_tools::HomogeneousTuple;
+/// let t = (1, 2, 3).map(|x| x * 2);
+/// assert_eq!(t, (2, 4, 6));
+/// assert_eq!(t.fold(0, |acc, x| acc + x), 12);
+/// assert_eq!(t.to_array(), [2, 4, 6]);
+/// assert_eq!(<(_, _, _)>::from_array(['a', 'b', 'c']), ('a', 'b', 'c'));
+/// ```
+pub trait HomogeneousTuple: Sized {
+    /// The type of all the elements
+    type Item;
+    /// The array with the same elements, `[Self::Item; Self::LEN]`
+    type Array;
+    /// The tuple of the same size with elements of type `U`
+    type Mapped<U>;
+
+    /// The number of elements
+    const LEN: usize;
+
+    /// Applies `f` to every element (from left to right)
+    fn map<U, F: FnMut(Self::Item) -> U>(self, f: F) -> Self::Mapped<U>;
+
+    /// Folds all elements (from left to right) into an accumulator,
+    /// like [`Iterator::fold`](https://doc.rust-lang.org/std/iter/trait.Iterator.html#method.fold)
+    fn fold<B, F: FnMut(B, Self::Item) -> B>(self, init: B, f: F) -> B;
+
+    /// Converts the tuple into an array
+    fn to_array(self) -> Self::Array;
+
+    /// Converts an array into a tuple
+    fn from_array(array: Self::Array) -> Self;
+}
+
+macro_rules! impl_homogeneous_tuple {
+    (@ty $t:ident $bind:ident) => {
+        $t
+    };
+    ($len:literal => $($bind:ident),+) => {
+        impl<T> HomogeneousTuple for ($(impl_homogeneous_tuple!(@ty T $bind),)+) {
+            type Item = T;
+            type Array = [T; $len];
+            type Mapped<U> = ($(impl_homogeneous_tuple!(@ty U $bind),)+);
+
+            const LEN: usize = $len;
+
+            fn map<U, F: FnMut(T) -> U>(self, mut f: F) -> Self::Mapped<U> {
+                let ($($bind,)+) = self;
+                ($(f($bind),)+)
+            }
+
+            fn fold<B, F: FnMut(B, T) -> B>(self, init: B, mut f: F) -> B {
+                let ($($bind,)+) = self;
+                let acc = init;
+                $(
+                    let acc = f(acc, $bind);
+                )+
+                acc
+            }
+
+            fn to_array(self) -> Self::Array {
+                let ($($bind,)+) = self;
+                [$($bind),+]
+            }
+
+            fn from_array(array: Self::Array) -> Self {
+                let [$($bind),+] = array;
+                ($($bind,)+)
+            }
+        }
+    };
+}
+
+impl_homogeneous_tuple!(1 => a);
+impl_homogeneous_tuple!(2 => a, b);
+impl_homogeneous_tuple!(3 => a, b, c);
+impl_homogeneous_tuple!(4 => a, b, c, d);
+impl_homogeneous_tuple!(5 => a, b, c, d, e);
+impl_homogeneous_tuple!(6 => a, b, c, d, e, f);
+impl_homogeneous_tuple!(7 => a, b, c, d, e, f, g);
+impl_homogeneous_tuple!(8 => a, b, c, d, e, f, g, h);
+impl_homogeneous_tuple!(9 => a, b, c, d, e, f, g, h, i);
+impl_homogeneous_tuple!(10 => a, b, c, d, e, f, g, h, i, j);
+impl_homogeneous_tuple!(11 => a, b, c, d, e, f, g, h, i, j, k);
+impl_homogeneous_tuple!(12 => a, b, c, d, e, f, g, h, i, j, k, l);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn nest_and_flatten() {
+        let t = (0, 'a', "b", 1.0, 2u8, -3, 'c', "d", 4.0, 5u8, -6, 'e');
+        let nested = t.nest();
+        assert_eq!(
+            nested,
+            (
+                0,
+                (
+                    'a',
+                    (
+                        "b",
+                        (1.0, (2u8, (-3, ('c', ("d", (4.0, (5u8, (-6, 'e'))))))))
+                    )
+                )
+            )
+        );
+        let flat: (_, _, _, _, _, _, _, _, _, _, _, _) = nested.flatten();
+        assert_eq!(flat, t);
+
+        assert_eq!((1, 2).nest(), (1, 2));
+        let flat: (_, _) = (1, 2).flatten();
+        assert_eq!(flat, (1, 2));
+    }
+
+    #[test]
+    fn homogeneous() {
+        assert_eq!((1,).map(|x| x + 1), (2,));
+        assert_eq!((1, 2, 3, 4).fold(0, |acc, x| acc * 10 + x), 1234);
+        assert_eq!(<(u8, u8, u8, u8) as HomogeneousTuple>::LEN, 4);
+
+        let t = (0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11);
+        let arr = t.to_array();
+        assert_eq!(arr, [0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11]);
+        let t2: (i32, i32, i32, i32, i32, i32, i32, i32, i32, i32, i32, i32) =
+            HomogeneousTuple::from_array(arr);
+        assert_eq!(t2, t);
+    }
+}
@@ -1,6 +1,126 @@
+use crate::flatten_tuple_fn;
+use std::error::Error;
+use std::fmt;
+
 /// Generates a new `Vec` out of several `Vec`s, where each new element is a tuple of the previous `Vec`s' elements at that index
 pub fn zip<A, B>(a: Vec<A>, b: Vec<B>) -> Vec<(A, B)> {
-    a.into_iter().zip(b.into_iter()).collect()
+    a.into_iter().zip(b).collect()
+}
+
+/// Like [`zip`](#function.zip), but returns an error instead of truncating
+/// when the `Vec`s don't all have the same length
+pub fn try_zip<A, B>(a: Vec<A>, b: Vec<B>) -> Result<Vec<(A, B)>, ZipLengthMismatch> {
+    ZipLengthMismatch::check(&[a.len(), b.len()])?;
+    Ok(zip(a, b))
+}
+
+/// Like [`zip`](#function.zip), but instead of stopping at the end of the shortest `Vec`,
+/// it keeps going until the longest one runs out, filling the gaps with `None`
+pub fn zip_longest<A, B>(a: Vec<A>, b: Vec<B>) -> Vec<(Option<A>, Option<B>)> {
+    let len = a.len().max(b.len());
+    let (mut a, mut b) = (a.into_iter(), b.into_iter());
+    (0..len).map(|_| (a.next(), b.next())).collect()
+}
+
+/// Like [`zip`](#function.zip), but borrows the elements instead of consuming the `Vec`s
+pub fn zip_ref<'a, A, B>(a: &'a [A], b: &'a [B]) -> Vec<(&'a A, &'a B)> {
+    a.iter().zip(b).collect()
+}
+
+/// Like [`unzip`](#function.unzip), but borrows the elements instead of consuming the `Vec`
+pub fn unzip_ref<A, B>(v: &[(A, B)]) -> (Vec<&A>, Vec<&B>) {
+    v.iter().map(|(a, b)| (a, b)).unzip()
+}
+
+/// Like [`unzip`](#function.unzip), but clones the elements instead of consuming the `Vec`
+pub fn unzip_cloned<A: Clone, B: Clone>(v: &[(A, B)]) -> (Vec<A>, Vec<B>) {
+    v.iter().cloned().unzip()
+}
+
+/// Like [`unzip`](#function.unzip) (and its variants), but collects into any collections
+/// that implement `Default` and `Extend` (e.g. `HashSet`, `VecDeque`, `String`)
+///
+/// This works for tuples with up to 12 elements.
+/// The collections reserve capacity according to the iterator's `size_hint`.
+///
+/// Example:
+/// ```
+/// # use tlib::vec_zip::unzip_into;
+/// # use std::collections::{HashSet, VecDeque};
+/// let v = vec![(1, 'a', 2.0), (1, 'b', 3.0)];
+/// let (a, b, c): (HashSet<_>, String, VecDeque<_>) = unzip_into(v);
+/// assert_eq!(a.len(), 1);
+/// assert_eq!(b, "ab");
+/// assert_eq!(c, [2.0, 3.0]);
+/// ```
+pub fn unzip_into<C: Default + Extend<T>, T>(iter: impl IntoIterator<Item = T>) -> C {
+    let mut c = C::default();
+    c.extend(iter);
+    c
+}
+
+/// Like [`unzip_into`](#function.unzip_into), but for an iterator of `Result`s
+///
+/// Stops at the first error and returns it, otherwise returns the collections.
+///
+/// Example:
+/// ```
+/// # use tlib::vec_zip::try_unzip_into;
+/// # use std::collections::BTreeSet;
+/// let lines = ["1 a", "2 b", "x c"];
+/// let parse = |s: &str| -> Result<(u8, String), std::num::ParseIntError> {
+///     let (n, rest) = s.split_at(1);
+///     Ok((n.parse()?, rest.trim().to_string()))
+/// };
+///
+/// let res: Result<(BTreeSet<_>, Vec<_>), _> = try_unzip_into(lines[..2].iter().map(|s| parse(s)));
+/// assert_eq!(res.unwrap().0.len(), 2);
+/// let res: Result<(BTreeSet<_>, Vec<_>), _> = try_unzip_into(lines.iter().map(|s| parse(s)));
+/// assert!(res.is_err());
+/// ```
+pub fn try_unzip_into<C: Default + Extend<T>, T, E>(
+    iter: impl IntoIterator<Item = Result<T, E>>,
+) -> Result<C, E> {
+    let mut err = None;
+    let c = unzip_into(iter.into_iter().map_while(|res| match res {
+        Ok(t) => Some(t),
+        Err(e) => {
+            err = Some(e);
+            None
+        }
+    }));
+    match err {
+        Some(e) => Err(e),
+        None => Ok(c),
+    }
+}
+
+/// Like [`unzip`](#function.unzip), but for an iterator of `Result`s (e.g. from parsing),
+/// stopping at the first error and returning it
+pub fn try_unzip<A, B, E>(
+    iter: impl IntoIterator<Item = Result<(A, B), E>>,
+) -> Result<(Vec<A>, Vec<B>), E> {
+    try_unzip_into(iter)
+}
+
+/// Like [`zip_longest`](#function.zip_longest), but fills the gaps with clones of the given default values
+pub fn zip_longest_with<A: Clone, B: Clone>(
+    a: Vec<A>,
+    b: Vec<B>,
+    default_a: A,
+    default_b: B,
+) -> Vec<(A, B)> {
+    let len = a.len().max(b.len());
+    let mut a = a.into_iter();
+    let mut b = b.into_iter();
+    (0..len)
+        .map(|_| {
+            (
+                a.next().unwrap_or_else(|| default_a.clone()),
+                b.next().unwrap_or_else(|| default_b.clone()),
+            )
+        })
+        .collect()
 }
 
 /// Generates several `Vec`s out of a `Vec` (where each element is a tuple), splitting the tuple elements all into single types
@@ -8,10 +128,53 @@ pub fn unzip<A, B>(v: Vec<(A, B)>) -> (Vec<A>, Vec<B>) {
     v.into_iter().unzip()
 }
 
+/// The error returned by [`try_zip`](#function.try_zip) and its variants
+/// when the `Vec`s don't all have the same length
+///
+/// This is also used by the other functions in this module that require equal lengths
+/// (e.g. [`try_transpose`](fn.try_transpose.html) or [`interleave`](fn.interleave.html)),
+/// so its message doesn't mention zipping
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+pub struct ZipLengthMismatch {
+    lengths: Vec<usize>,
+}
+
+impl ZipLengthMismatch {
+    fn check(lengths: &[usize]) -> Result<(), Self> {
+        if lengths.windows(2).all(|w| w[0] == w[1]) {
+            Ok(())
+        } else {
+            Err(Self {
+                lengths: lengths.to_vec(),
+            })
+        }
+    }
+
+    /// The lengths of all the input `Vec`s, in argument order
+    pub fn lengths(&self) -> &[usize] {
+        &self.lengths
+    }
+}
+
+impl fmt::Display for ZipLengthMismatch {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "expected Vecs of equal length, got lengths {:?}",
+            self.lengths
+        )
+    }
+}
+
+impl Error for ZipLengthMismatch {}
+
 /// The same as the [`zip`](#function.zip) function but called as `Vec::zip`
 pub trait VecZipTrait<T, U> {
     #[allow(missing_docs)]
     fn zip(self, other: Vec<U>) -> Vec<(T, U)>;
+
+    /// The same as the [`try_zip`](#function.try_zip) function but called as `Vec::try_zip`
+    fn try_zip(self, other: Vec<U>) -> Result<Vec<(T, U)>, ZipLengthMismatch>;
 }
 
 /// The same as the [`unzip`](#function.unzip) function but called as `Vec::unzip`
@@ -24,6 +187,10 @@ impl<T, U> VecZipTrait<T, U> for Vec<T> {
     fn zip(self, other: Vec<U>) -> Vec<(T, U)> {
         zip(self, other)
     }
+
+    fn try_zip(self, other: Vec<U>) -> Result<Vec<(T, U)>, ZipLengthMismatch> {
+        try_zip(self, other)
+    }
 }
 
 impl<T, U> VecUnzipTrait<T, U> for Vec<(T, U)> {
@@ -32,35 +199,55 @@ impl<T, U> VecUnzipTrait<T, U> for Vec<(T, U)> {
     }
 }
 
-// note: maybe consider adding traits for the other numbers?
+/// The same as the [`zip`](#function.zip) function (and its variants) but called as a method on a tuple of `Vec`s
+///
+/// This is implemented for tuples of 2 to 10 `Vec`s
+///
+/// Example:
+/// ```
+/// # use tlib::vec_zip::TupleZip;
+/// let v = (vec![1, 2], vec!['a', 'b'], vec!["x", "y"]).zip();
+/// assert_eq!(v, vec![(1, 'a', "x"), (2, 'b', "y")]);
+/// ```
+pub trait TupleZip {
+    /// The zipped `Vec`
+    type Output;
 
-// Macros for generating the zip functions
-macro_rules! __flatten1 {
-    ($first:ident; $($bind:ident),*) => {
-        |($first, ($($bind),*))| ($first, $($bind),*)
-    };
+    #[allow(missing_docs)]
+    fn zip(self) -> Self::Output;
 }
 
-macro_rules! flatten_tuple_fn {
-    ($a:ident, $b:ident, $c:ident) => {
-        __flatten1!($a; $b, $c)
-    };
-    ($first:ident $(,$bind:ident)*) => {
-        |(x, tup)| {
-            let f1 = flatten_tuple_fn!($($bind),*);
-            let flat = f1(tup);
-            let f2 = __flatten1!($first; $($bind),*);
-            f2((x, flat))
-        }
-    }
+/// The same as the `unzip*` functions but called as `Vec::unzip`, for tuples with more than two elements
+///
+/// This is implemented for `Vec`s of tuples with 3 to 10 elements
+/// (for pairs, use [`VecUnzipTrait`](trait.VecUnzipTrait.html))
+///
+/// Example:
+/// ```
+/// # use tlib::vec_zip::TupleUnzip;
+/// let (a, b, c) = vec![(1, 'a', "x"), (2, 'b', "y")].unzip();
+/// assert_eq!(a, vec![1, 2]);
+/// assert_eq!(b, vec!['a', 'b']);
+/// assert_eq!(c, vec!["x", "y"]);
+/// ```
+pub trait TupleUnzip {
+    /// The tuple of `Vec`s
+    type Output;
+
+    #[allow(missing_docs)]
+    fn unzip(self) -> Self::Output;
 }
 
+// Macros for generating the zip functions
+// (`zip_rec` is exported because the `zip!` macro needs it)
+#[doc(hidden)]
+#[macro_export]
 macro_rules! zip_rec {
-    ($a:ident, $b:ident) => {
-        $a.into_iter().zip($b.into_iter())
+    ($a:expr, $b:expr) => {
+        ::std::iter::IntoIterator::into_iter($a).zip($b)
     };
-    ($head:ident $(, $tail:ident)*) => {
-        $head.into_iter().zip(zip_rec!($($tail),*))
+    ($head:expr $(, $tail:expr)*) => {
+        ::std::iter::IntoIterator::into_iter($head).zip($crate::zip_rec!($($tail),*))
     };
 }
 
@@ -80,51 +267,279 @@ macro_rules! gen_unzip_fn {
     ($fname:ident => $($binding:ident @ $vname:ident: $t:ident),*) => {
         /// Like [`unzip`](#function.unzip) but with more `Vec`s
         pub fn $fname<$($t),*>(v: Vec<($($t),*)>) -> ($(Vec<$t>),*) {
+            unzip_into(v)
+        }
+    };
+}
+
+macro_rules! gen_try_zip_fn {
+    ($fname:ident => $($binding:ident @ $param:ident: $t:ident),*) => {
+        /// Like [`try_zip`](#function.try_zip) but with more `Vec`s
+        #[allow(clippy::too_many_arguments)]
+        pub fn $fname<$($t),*>($($param: Vec<$t>),*) -> Result<Vec<($($t),*)>, ZipLengthMismatch> {
+            ZipLengthMismatch::check(&[$($param.len()),*])?;
+            Ok(zip_rec!($($param),*).map(flatten_tuple_fn!($($binding),*)).collect())
+        }
+    };
+}
+
+macro_rules! gen_try_unzip_fn {
+    ($fname:ident => $($binding:ident @ $param:ident: $t:ident),*) => {
+        /// Like [`try_unzip`](#function.try_unzip) but with bigger tuples
+        #[allow(clippy::type_complexity)]
+        pub fn $fname<$($t,)* Err>(
+            iter: impl IntoIterator<Item = Result<($($t),*), Err>>,
+        ) -> Result<($(Vec<$t>),*), Err> {
+            try_unzip_into(iter)
+        }
+    };
+}
+
+macro_rules! gen_zip_longest_fn {
+    ($fname:ident => $($binding:ident @ $param:ident: $t:ident),*) => {
+        /// Like [`zip_longest`](#function.zip_longest) but with more `Vec`s
+        #[allow(clippy::too_many_arguments)]
+        pub fn $fname<$($t),*>($($param: Vec<$t>),*) -> Vec<($(Option<$t>),*)> {
+            let len = 0 $(.max($param.len()))*;
             $(
-                let mut $vname = Vec::new();
+                let mut $param = $param.into_iter();
             )*
-            for ($($binding),*) in v {
-                $(
-                    $vname.push($binding);
-                )*
+            (0..len).map(|_| ($($param.next()),*)).collect()
+        }
+    };
+}
+
+macro_rules! gen_zip_longest_with_fn {
+    ($fname:ident => $($binding:ident @ $param:ident: $t:ident),*) => {
+        /// Like [`zip_longest_with`](#function.zip_longest_with) but with more `Vec`s
+        ///
+        /// The first half of the arguments are the `Vec`s, the second half are the default values (in the same order)
+        #[allow(clippy::too_many_arguments)]
+        pub fn $fname<$($t: Clone),*>($($param: Vec<$t>,)* $($binding: $t),*) -> Vec<($($t),*)> {
+            let len = 0 $(.max($param.len()))*;
+            $(
+                let mut $param = $param.into_iter();
+            )*
+            (0..len)
+                .map(|_| ($($param.next().unwrap_or_else(|| $binding.clone())),*))
+                .collect()
+        }
+    };
+}
+
+macro_rules! gen_zip_ref_fn {
+    ($fname:ident => $($binding:ident @ $param:ident: $t:ident),*) => {
+        /// Like [`zip_ref`](#function.zip_ref) but with more slices
+        #[allow(clippy::too_many_arguments)]
+        pub fn $fname<'a, $($t),*>($($param: &'a [$t]),*) -> Vec<($(&'a $t),*)> {
+            zip_rec!($($param),*).map(flatten_tuple_fn!($($binding),*)).collect()
+        }
+    };
+}
+
+macro_rules! gen_unzip_ref_fn {
+    ($fname:ident => $($binding:ident @ $param:ident: $t:ident),*) => {
+        /// Like [`unzip_ref`](#function.unzip_ref) but with bigger tuples
+        pub fn $fname<$($t),*>(v: &[($($t),*)]) -> ($(Vec<&$t>),*) {
+            unzip_into(v.iter().map(|($($binding),*)| ($($binding),*)))
+        }
+    };
+}
+
+macro_rules! gen_unzip_cloned_fn {
+    ($fname:ident => $($binding:ident @ $param:ident: $t:ident),*) => {
+        /// Like [`unzip_cloned`](#function.unzip_cloned) but with bigger tuples
+        pub fn $fname<$($t: Clone),*>(v: &[($($t),*)]) -> ($(Vec<$t>),*) {
+            unzip_into(v.iter().cloned())
+        }
+    };
+}
+
+macro_rules! gen_tuple_zip_impl {
+    ($fname:ident => $($binding:ident @ $param:ident: $t:ident),*) => {
+        impl<$($t),*> TupleZip for ($(Vec<$t>),*) {
+            type Output = Vec<($($t),*)>;
+
+            fn zip(self) -> Self::Output {
+                let ($($param),*) = self;
+                $fname($($param),*)
             }
-            ($($vname),*)
         }
     };
 }
 
-// Unified macro to generate zip AND unzip functions
+macro_rules! gen_tuple_unzip_impl {
+    ($fname:ident => $($binding:ident @ $param:ident: $t:ident),*) => {
+        impl<$($t),*> TupleUnzip for Vec<($($t),*)> {
+            type Output = ($(Vec<$t>),*);
 
-macro_rules! gen_zip_and_unzip_fns_accum {
-    ($fname:ident, $ufname:ident => $($binding:ident @ $extra:ident: $t:ident),*; $($fname2:ident, $ufname2:ident => $($binding2:ident @ $extra2:ident: $t2:ident),*;)*) => {
-        gen_zip_and_unzip_fns_accum!([] $fname, $ufname => $($binding @ $extra: $t),*; $($fname2, $ufname2 => $($binding2 @ $extra2: $t2),*;)*);
+            fn unzip(self) -> Self::Output {
+                $fname(self)
+            }
+        }
     };
-    ([$($binding1:ident @ $extra1:ident: $t1:ident,)*] $fname:ident, $ufname:ident => $($binding:ident @ $extra:ident: $t:ident),*;) => {
-        gen_zip_fn!($fname => $($binding1 @ $extra1: $t1,)* $($binding @ $extra: $t),*);
-        gen_unzip_fn!($ufname => $($binding1 @ $extra1: $t1,)* $($binding @ $extra: $t),*);
+}
+
+// Macro to generate a family of functions (one for each arity) with one of the macros above
+// (`$fname` can also be a parenthesized group, for generators that need more than one name)
+
+macro_rules! gen_fns_accum {
+    ($gen:ident; $($fname:tt => $($binding:ident @ $extra:ident: $t:ident),*;)*) => {
+        gen_fns_accum!($gen; [] $($fname => $($binding @ $extra: $t),*;)*);
     };
-    ([$($binding1:ident @ $extra1:ident: $t1:ident,)*] $fname:ident, $ufname:ident => $($binding:ident @ $extra:ident: $t:ident),*; $($fname2:ident, $ufname2:ident => $($binding2:ident @ $extra2:ident: $t2:ident),*;)*) => {
-        gen_zip_fn!($fname => $($binding1 @ $extra1: $t1,)* $($binding @ $extra: $t),*);
-        gen_unzip_fn!($ufname => $($binding1 @ $extra1: $t1,)* $($binding @ $extra: $t),*);
-        gen_zip_and_unzip_fns_accum!([$($binding1 @ $extra1: $t1,)* $($binding @ $extra: $t,)*] $($fname2, $ufname2 => $($binding2 @ $extra2: $t2),*;)*);
+    ($gen:ident; [$($binding1:ident @ $extra1:ident: $t1:ident,)*]) => {};
+    ($gen:ident; [$($binding1:ident @ $extra1:ident: $t1:ident,)*] $fname:tt => $($binding:ident @ $extra:ident: $t:ident),*; $($fname2:tt => $($binding2:ident @ $extra2:ident: $t2:ident),*;)*) => {
+        $gen!($fname => $($binding1 @ $extra1: $t1,)* $($binding @ $extra: $t),*);
+        gen_fns_accum!($gen; [$($binding1 @ $extra1: $t1,)* $($binding @ $extra: $t,)*] $($fname2 => $($binding2 @ $extra2: $t2),*;)*);
     };
 }
 
 // Generated functions
-gen_zip_and_unzip_fns_accum! {
-    zip3, unzip3 => t1 @ a: A, t2 @ b: B, t3 @ c: C;
-    zip4, unzip4 => t4 @ d: D;
-    zip5, unzip5 => t5 @ e: E;
-    zip6, unzip6 => t6 @ f: F;
-    zip7, unzip7 => t7 @ g: G;
-    zip8, unzip8 => t8 @ h: H;
-    zip9, unzip9 => t9 @ i: I;
-    zip10, unzip10 => t10 @ j: J;
+gen_fns_accum! { gen_zip_fn;
+    zip3 => t1 @ a: A, t2 @ b: B, t3 @ c: C;
+    zip4 => t4 @ d: D;
+    zip5 => t5 @ e: E;
+    zip6 => t6 @ f: F;
+    zip7 => t7 @ g: G;
+    zip8 => t8 @ h: H;
+    zip9 => t9 @ i: I;
+    zip10 => t10 @ j: J;
+}
+
+gen_fns_accum! { gen_unzip_fn;
+    unzip3 => t1 @ a: A, t2 @ b: B, t3 @ c: C;
+    unzip4 => t4 @ d: D;
+    unzip5 => t5 @ e: E;
+    unzip6 => t6 @ f: F;
+    unzip7 => t7 @ g: G;
+    unzip8 => t8 @ h: H;
+    unzip9 => t9 @ i: I;
+    unzip10 => t10 @ j: J;
+}
+
+gen_fns_accum! { gen_try_zip_fn;
+    try_zip3 => t1 @ a: A, t2 @ b: B, t3 @ c: C;
+    try_zip4 => t4 @ d: D;
+    try_zip5 => t5 @ e: E;
+    try_zip6 => t6 @ f: F;
+    try_zip7 => t7 @ g: G;
+    try_zip8 => t8 @ h: H;
+    try_zip9 => t9 @ i: I;
+    try_zip10 => t10 @ j: J;
+}
+
+gen_fns_accum! { gen_try_unzip_fn;
+    try_unzip3 => t1 @ a: A, t2 @ b: B, t3 @ c: C;
+    try_unzip4 => t4 @ d: D;
+    try_unzip5 => t5 @ e: E;
+    try_unzip6 => t6 @ f: F;
+    try_unzip7 => t7 @ g: G;
+    try_unzip8 => t8 @ h: H;
+    try_unzip9 => t9 @ i: I;
+    try_unzip10 => t10 @ j: J;
+}
+
+gen_fns_accum! { gen_zip_longest_fn;
+    zip_longest3 => t1 @ a: A, t2 @ b: B, t3 @ c: C;
+    zip_longest4 => t4 @ d: D;
+    zip_longest5 => t5 @ e: E;
+    zip_longest6 => t6 @ f: F;
+    zip_longest7 => t7 @ g: G;
+    zip_longest8 => t8 @ h: H;
+    zip_longest9 => t9 @ i: I;
+    zip_longest10 => t10 @ j: J;
+}
+
+gen_fns_accum! { gen_zip_longest_with_fn;
+    zip_longest_with3 => t1 @ a: A, t2 @ b: B, t3 @ c: C;
+    zip_longest_with4 => t4 @ d: D;
+    zip_longest_with5 => t5 @ e: E;
+    zip_longest_with6 => t6 @ f: F;
+    zip_longest_with7 => t7 @ g: G;
+    zip_longest_with8 => t8 @ h: H;
+    zip_longest_with9 => t9 @ i: I;
+    zip_longest_with10 => t10 @ j: J;
 }
 
+gen_fns_accum! { gen_zip_ref_fn;
+    zip_ref3 => t1 @ a: A, t2 @ b: B, t3 @ c: C;
+    zip_ref4 => t4 @ d: D;
+    zip_ref5 => t5 @ e: E;
+    zip_ref6 => t6 @ f: F;
+    zip_ref7 => t7 @ g: G;
+    zip_ref8 => t8 @ h: H;
+    zip_ref9 => t9 @ i: I;
+    zip_ref10 => t10 @ j: J;
+}
+
+gen_fns_accum! { gen_unzip_ref_fn;
+    unzip_ref3 => t1 @ a: A, t2 @ b: B, t3 @ c: C;
+    unzip_ref4 => t4 @ d: D;
+    unzip_ref5 => t5 @ e: E;
+    unzip_ref6 => t6 @ f: F;
+    unzip_ref7 => t7 @ g: G;
+    unzip_ref8 => t8 @ h: H;
+    unzip_ref9 => t9 @ i: I;
+    unzip_ref10 => t10 @ j: J;
+}
+
+gen_fns_accum! { gen_unzip_cloned_fn;
+    unzip_cloned3 => t1 @ a: A, t2 @ b: B, t3 @ c: C;
+    unzip_cloned4 => t4 @ d: D;
+    unzip_cloned5 => t5 @ e: E;
+    unzip_cloned6 => t6 @ f: F;
+    unzip_cloned7 => t7 @ g: G;
+    unzip_cloned8 => t8 @ h: H;
+    unzip_cloned9 => t9 @ i: I;
+    unzip_cloned10 => t10 @ j: J;
+}
+
+gen_fns_accum! { gen_tuple_zip_impl;
+    zip => t1 @ a: A, t2 @ b: B;
+    zip3 => t3 @ c: C;
+    zip4 => t4 @ d: D;
+    zip5 => t5 @ e: E;
+    zip6 => t6 @ f: F;
+    zip7 => t7 @ g: G;
+    zip8 => t8 @ h: H;
+    zip9 => t9 @ i: I;
+    zip10 => t10 @ j: J;
+}
+
+gen_fns_accum! { gen_tuple_unzip_impl;
+    unzip3 => t1 @ a: A, t2 @ b: B, t3 @ c: C;
+    unzip4 => t4 @ d: D;
+    unzip5 => t5 @ e: E;
+    unzip6 => t6 @ f: F;
+    unzip7 => t7 @ g: G;
+    unzip8 => t8 @ h: H;
+    unzip9 => t9 @ i: I;
+    unzip10 => t10 @ j: J;
+}
+
+mod columns;
+mod group;
+mod interleave;
+mod join;
+mod lockstep;
+#[cfg(feature = "rayon")]
+mod par;
+mod partition;
+mod transpose;
+
+pub use self::columns::*;
+pub use self::group::*;
+pub use self::interleave::*;
+pub use self::join::*;
+pub use self::lockstep::*;
+#[cfg(feature = "rayon")]
+pub use self::par::*;
+pub use self::partition::*;
+pub use self::transpose::*;
+
 #[cfg(test)]
 mod test {
-    use super::{unzip10, zip10};
+    use super::*;
 
     macro_rules! vecs {
         ($($a:literal, $b:literal),*) => {
@@ -164,4 +579,186 @@ mod test {
             unzip10(v)
         )
     }
+
+    #[test]
+    fn test_zip_macro() {
+        assert_eq!(
+            vec![(0, 'a'), (1, 'b')],
+            crate::zip!(vec![0, 1], vec!['a', 'b'])
+        );
+
+        let (a, b, c, d, e, f, g, h, i, j, k, l, m, n, o, p) = vecs![
+            0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16, 17, 18, 19, 20, 21, 22, 23,
+            24, 25, 26, 27, 28, 29, 30, 31
+        ];
+
+        let v = crate::zip!(a, b, c, d, e, f, g, h, i, j, k, l, m, n, o, p);
+
+        // tuples this large don't implement `PartialEq` or `Debug`
+        let v: Vec<_> = v
+            .into_iter()
+            .map(|(a, b, c, d, e, f, g, h, i, j, k, l, m, n, o, p)| {
+                [a, b, c, d, e, f, g, h, i, j, k, l, m, n, o, p]
+            })
+            .collect();
+
+        assert_eq!(
+            vec![
+                [0, 2, 4, 6, 8, 10, 12, 14, 16, 18, 20, 22, 24, 26, 28, 30],
+                [1, 3, 5, 7, 9, 11, 13, 15, 17, 19, 21, 23, 25, 27, 29, 31]
+            ],
+            v
+        );
+    }
+
+    #[test]
+    fn test_unzip_macro() {
+        assert_eq!(
+            (vec![0, 1], vec!['a', 'b']),
+            crate::unzip!(vec![(0, 'a'), (1, 'b')] => _, _)
+        );
+
+        let v = vec![
+            (0, 2, 4, 6, 8, 10, 12, 14, 16, 18, 20, 22, 24, 26, 28, 30),
+            (1, 3, 5, 7, 9, 11, 13, 15, 17, 19, 21, 23, 25, 27, 29, 31),
+        ];
+
+        let (a, b, c, d, e, f, g, h, i, j, k, l, m, n, o, p) =
+            crate::unzip!(v => _, _, _, _, _, _, _, _, _, _, _, _, _, _, _, _);
+
+        assert_eq!(
+            [
+                vec![0, 1],
+                vec![2, 3],
+                vec![4, 5],
+                vec![6, 7],
+                vec![8, 9],
+                vec![10, 11],
+                vec![12, 13],
+                vec![14, 15],
+                vec![16, 17],
+                vec![18, 19],
+                vec![20, 21],
+                vec![22, 23],
+                vec![24, 25],
+                vec![26, 27],
+                vec![28, 29],
+                vec![30, 31]
+            ],
+            [a, b, c, d, e, f, g, h, i, j, k, l, m, n, o, p]
+        );
+    }
+
+    #[test]
+    fn test_try_zip() {
+        assert_eq!(
+            Ok(vec![(0, 'a'), (1, 'b')]),
+            try_zip(vec![0, 1], vec!['a', 'b'])
+        );
+        assert_eq!(
+            Ok(vec![(0, 'a'), (1, 'b')]),
+            vec![0, 1].try_zip(vec!['a', 'b'])
+        );
+
+        let err = try_zip3(vec![0, 1], vec!['a', 'b', 'c'], vec![2]).unwrap_err();
+        assert_eq!(&[2, 3, 1], err.lengths());
+        assert_eq!(
+            Err(ZipLengthMismatch {
+                lengths: vec![2, 1]
+            }),
+            vec![0, 1].try_zip(vec!['a'])
+        );
+    }
+
+    #[test]
+    fn test_zip_longest() {
+        assert_eq!(
+            vec![(Some(0), Some('a')), (Some(1), None)],
+            zip_longest(vec![0, 1], vec!['a'])
+        );
+        assert_eq!(
+            vec![(0, 'a'), (1, 'z')],
+            zip_longest_with(vec![0, 1], vec!['a'], -1, 'z')
+        );
+
+        assert_eq!(
+            vec![
+                (Some(0), Some('a'), Some(2)),
+                (None, Some('b'), Some(3)),
+                (None, None, Some(4))
+            ],
+            zip_longest3(vec![0], vec!['a', 'b'], vec![2, 3, 4])
+        );
+        assert_eq!(
+            vec![(0, 'a', 2), (-1, 'b', 3), (-1, 'z', 4)],
+            zip_longest_with3(vec![0], vec!['a', 'b'], vec![2, 3, 4], -1, 'z', -2)
+        );
+    }
+
+    #[test]
+    fn test_unzip_into() {
+        use std::collections::{BTreeMap, VecDeque};
+
+        let v = vec![((0, 'a'), "x", 1), ((1, 'b'), "y", 2)];
+        let (map, s, deque): (BTreeMap<_, _>, String, VecDeque<_>) = unzip_into(v);
+        assert_eq!(map.get(&1), Some(&'b'));
+        assert_eq!(s, "xy");
+        assert_eq!(deque, VecDeque::from(vec![1, 2]));
+
+        let (a, b): (Vec<_>, Vec<_>) = unzip_into((0..4).map(|i| (i, i * 2)));
+        assert!(a.capacity() >= 4 && b.capacity() >= 4);
+        assert_eq!(b, vec![0, 2, 4, 6]);
+    }
+
+    #[test]
+    fn test_tuple_traits() {
+        assert_eq!(vec![(0, 'a')], (vec![0], vec!['a']).zip());
+
+        let t = vecs![0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16, 17, 18, 19];
+        let v = t.clone().zip();
+        assert_eq!(v, zip10(t.0, t.1, t.2, t.3, t.4, t.5, t.6, t.7, t.8, t.9));
+        assert_eq!(
+            vecs![0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16, 17, 18, 19],
+            v.unzip()
+        );
+
+        assert_eq!((vec![0], vec!['a'], vec![2]), vec![(0, 'a', 2)].unzip());
+    }
+
+    #[test]
+    fn test_ref_variants() {
+        let (a, b, c) = (vec![0, 1], vec!['a', 'b', 'c'], vec![2, 3]);
+        assert_eq!(vec![(&0, &'a'), (&1, &'b')], zip_ref(&a, &b));
+        assert_eq!(vec![(&0, &'a', &2), (&1, &'b', &3)], zip_ref3(&a, &b, &c));
+
+        let v = vec![(0, 'a'), (1, 'b')];
+        assert_eq!((vec![&0, &1], vec![&'a', &'b']), unzip_ref(&v));
+        assert_eq!((vec![0, 1], vec!['a', 'b']), unzip_cloned(&v));
+
+        let v = vec![(0, 'a', 2), (1, 'b', 3)];
+        assert_eq!(
+            (vec![&0, &1], vec![&'a', &'b'], vec![&2, &3]),
+            unzip_ref3(&v)
+        );
+        assert_eq!((vec![0, 1], vec!['a', 'b'], vec![2, 3]), unzip_cloned3(&v));
+    }
+
+    #[test]
+    fn test_try_unzip() {
+        let ok: Vec<Result<_, ()>> = vec![Ok((0, 'a', 2)), Ok((1, 'b', 3))];
+        assert_eq!(try_unzip3(ok), Ok((vec![0, 1], vec!['a', 'b'], vec![2, 3])));
+
+        // nothing after the first error is consumed
+        let mut consumed = 0;
+        let items = (0..5).map(|i| {
+            consumed += 1;
+            if i == 2 {
+                Err(i)
+            } else {
+                Ok((i, i))
+            }
+        });
+        assert_eq!(try_unzip(items), Err(2));
+        assert_eq!(consumed, 3);
+    }
 }
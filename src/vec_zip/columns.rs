@@ -0,0 +1,371 @@
+use super::*;
+use std::iter::FromIterator;
+use std::ops::Range;
+
+mod column_row_seal {
+    pub trait ColumnRowSeal {}
+}
+
+/// A tuple type that can be used as the row type of [`Columns`](struct.Columns.html)
+///
+/// This is implemented for tuples with 2 to 10 elements and can't be implemented outside of this crate.
+/// It only exists to name the row type in `Columns<T>`:
+/// its methods are the per-arity operations that `Columns` is built on, and aren't meant to be called directly.
+pub trait ColumnRow: Sized + column_row_seal::ColumnRowSeal {
+    /// The tuple of `Vec`s, one for each element of the row
+    type Columns;
+    /// The tuple of references to the elements of the row
+    type Ref<'a>
+    where
+        Self: 'a;
+
+    #[doc(hidden)]
+    fn empty_columns() -> Self::Columns;
+    #[doc(hidden)]
+    fn check_lengths(cols: &Self::Columns) -> Result<(), ZipLengthMismatch>;
+    #[doc(hidden)]
+    fn len(cols: &Self::Columns) -> usize;
+    #[doc(hidden)]
+    fn push(cols: &mut Self::Columns, row: Self);
+    #[doc(hidden)]
+    fn pop(cols: &mut Self::Columns) -> Option<Self>;
+    #[doc(hidden)]
+    fn insert(cols: &mut Self::Columns, index: usize, row: Self);
+    #[doc(hidden)]
+    fn remove(cols: &mut Self::Columns, index: usize) -> Self;
+    #[doc(hidden)]
+    fn get(cols: &Self::Columns, index: usize) -> Option<Self::Ref<'_>>;
+    #[doc(hidden)]
+    fn from_rows(rows: Vec<Self>) -> Self::Columns;
+    #[doc(hidden)]
+    fn into_rows(cols: Self::Columns) -> Vec<Self>;
+}
+
+/// A table of parallel `Vec`s (columns) that are guaranteed to have the same length
+///
+/// `T` is the row type, a tuple like `(A, B, C)`
+///
+/// Example:
+/// ```
+/// # use tlib::vec_zip::Columns;
+/// let mut cols = Columns::from(vec![(1, 'a'), (2, 'b')]);
+/// cols.push((3, 'c'));
+/// assert_eq!(cols.row(2), Some((&3, &'c')));
+/// assert_eq!(cols.columns().1, vec!['a', 'b', 'c']);
+/// assert_eq!(cols.remove(0), (1, 'a'));
+/// assert_eq!(Vec::from(cols), vec![(2, 'b'), (3, 'c')]);
+/// ```
+pub struct Columns<T: ColumnRow> {
+    cols: T::Columns,
+}
+
+impl<T: ColumnRow> Columns<T> {
+    /// Creates an empty table
+    pub fn new() -> Self {
+        Self {
+            cols: T::empty_columns(),
+        }
+    }
+
+    /// Creates a table from a tuple of columns,
+    /// returning an error if they don't all have the same length
+    pub fn try_from_columns(cols: T::Columns) -> Result<Self, ZipLengthMismatch> {
+        T::check_lengths(&cols)?;
+        Ok(Self { cols })
+    }
+
+    /// The number of rows
+    pub fn len(&self) -> usize {
+        T::len(&self.cols)
+    }
+
+    /// Returns whether there are no rows
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Appends a row
+    pub fn push(&mut self, row: T) {
+        T::push(&mut self.cols, row)
+    }
+
+    /// Removes the last row and returns it, or `None` if the table is empty
+    pub fn pop(&mut self) -> Option<T> {
+        T::pop(&mut self.cols)
+    }
+
+    /// Inserts a row at position `index`, shifting all rows after it
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index > len`
+    pub fn insert(&mut self, index: usize, row: T) {
+        assert!(
+            index <= self.len(),
+            "insertion index (is {}) should be <= len (is {})",
+            index,
+            self.len()
+        );
+        T::insert(&mut self.cols, index, row)
+    }
+
+    /// Removes and returns the row at position `index`, shifting all rows after it
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index` is out of bounds
+    pub fn remove(&mut self, index: usize) -> T {
+        assert!(
+            index < self.len(),
+            "removal index (is {}) should be < len (is {})",
+            index,
+            self.len()
+        );
+        T::remove(&mut self.cols, index)
+    }
+
+    /// Returns references to the elements of the row at `index`, or `None` if it is out of bounds
+    pub fn row(&self, index: usize) -> Option<T::Ref<'_>> {
+        T::get(&self.cols, index)
+    }
+
+    /// Returns an iterator over the rows (as tuples of references)
+    pub fn rows(&self) -> Rows<'_, T> {
+        Rows {
+            cols: &self.cols,
+            range: 0..self.len(),
+        }
+    }
+
+    /// Returns the tuple of columns
+    pub fn columns(&self) -> &T::Columns {
+        &self.cols
+    }
+
+    /// Converts the table into its tuple of columns
+    pub fn into_columns(self) -> T::Columns {
+        self.cols
+    }
+}
+
+impl<T: ColumnRow> Default for Columns<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: ColumnRow> Clone for Columns<T>
+where
+    T::Columns: Clone,
+{
+    fn clone(&self) -> Self {
+        Self {
+            cols: self.cols.clone(),
+        }
+    }
+}
+
+impl<T: ColumnRow> fmt::Debug for Columns<T>
+where
+    T::Columns: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("Columns").field("cols", &self.cols).finish()
+    }
+}
+
+impl<T: ColumnRow> PartialEq for Columns<T>
+where
+    T::Columns: PartialEq,
+{
+    fn eq(&self, other: &Self) -> bool {
+        self.cols == other.cols
+    }
+}
+
+impl<T: ColumnRow> Eq for Columns<T> where T::Columns: Eq {}
+
+impl<T: ColumnRow> From<Vec<T>> for Columns<T> {
+    fn from(rows: Vec<T>) -> Self {
+        Self {
+            cols: T::from_rows(rows),
+        }
+    }
+}
+
+impl<T: ColumnRow> From<Columns<T>> for Vec<T> {
+    fn from(cols: Columns<T>) -> Self {
+        T::into_rows(cols.cols)
+    }
+}
+
+impl<T: ColumnRow> FromIterator<T> for Columns<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut res = Self::new();
+        res.extend(iter);
+        res
+    }
+}
+
+impl<T: ColumnRow> Extend<T> for Columns<T> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        for row in iter {
+            self.push(row);
+        }
+    }
+}
+
+/// An iterator over the rows of [`Columns`](struct.Columns.html)
+pub struct Rows<'a, T: ColumnRow> {
+    cols: &'a T::Columns,
+    range: Range<usize>,
+}
+
+impl<'a, T: ColumnRow + 'a> Iterator for Rows<'a, T> {
+    type Item = T::Ref<'a>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let i = self.range.next()?;
+        T::get(self.cols, i)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.range.size_hint()
+    }
+}
+
+impl<'a, T: ColumnRow + 'a> DoubleEndedIterator for Rows<'a, T> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        let i = self.range.next_back()?;
+        T::get(self.cols, i)
+    }
+}
+
+impl<'a, T: ColumnRow + 'a> ExactSizeIterator for Rows<'a, T> {}
+
+macro_rules! gen_column_row_impl {
+    (($zip:ident, $unzip:ident) => $($binding:ident @ $param:ident: $t:ident),*) => {
+        impl<$($t),*> column_row_seal::ColumnRowSeal for ($($t),*) {}
+
+        impl<$($t),*> ColumnRow for ($($t),*) {
+            type Columns = ($(Vec<$t>),*);
+            type Ref<'a> = ($(&'a $t),*) where Self: 'a;
+
+            fn empty_columns() -> Self::Columns {
+                ($(Vec::<$t>::new()),*)
+            }
+
+            fn check_lengths(cols: &Self::Columns) -> Result<(), ZipLengthMismatch> {
+                let ($($param),*) = cols;
+                ZipLengthMismatch::check(&[$($param.len()),*])
+            }
+
+            fn len(cols: &Self::Columns) -> usize {
+                let ($($param),*) = cols;
+                [$($param.len()),*][0]
+            }
+
+            fn push(cols: &mut Self::Columns, row: Self) {
+                let ($($param),*) = cols;
+                let ($($binding),*) = row;
+                $(
+                    $param.push($binding);
+                )*
+            }
+
+            fn pop(cols: &mut Self::Columns) -> Option<Self> {
+                let ($($param),*) = cols;
+                Some(($($param.pop()?),*))
+            }
+
+            fn insert(cols: &mut Self::Columns, index: usize, row: Self) {
+                let ($($param),*) = cols;
+                let ($($binding),*) = row;
+                $(
+                    $param.insert(index, $binding);
+                )*
+            }
+
+            fn remove(cols: &mut Self::Columns, index: usize) -> Self {
+                let ($($param),*) = cols;
+                ($($param.remove(index)),*)
+            }
+
+            fn get(cols: &Self::Columns, index: usize) -> Option<Self::Ref<'_>> {
+                let ($($param),*) = cols;
+                Some(($($param.get(index)?),*))
+            }
+
+            fn from_rows(rows: Vec<Self>) -> Self::Columns {
+                $unzip(rows)
+            }
+
+            fn into_rows(cols: Self::Columns) -> Vec<Self> {
+                let ($($param),*) = cols;
+                $zip($($param),*)
+            }
+        }
+    };
+}
+
+gen_fns_accum! { gen_column_row_impl;
+    (zip, unzip) => t1 @ a: A, t2 @ b: B;
+    (zip3, unzip3) => t3 @ c: C;
+    (zip4, unzip4) => t4 @ d: D;
+    (zip5, unzip5) => t5 @ e: E;
+    (zip6, unzip6) => t6 @ f: F;
+    (zip7, unzip7) => t7 @ g: G;
+    (zip8, unzip8) => t8 @ h: H;
+    (zip9, unzip9) => t9 @ i: I;
+    (zip10, unzip10) => t10 @ j: J;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn row_operations() {
+        let mut cols = Columns::new();
+        assert!(cols.is_empty());
+        cols.push((1, 'a', "x"));
+        cols.push((3, 'c', "z"));
+        cols.insert(1, (2, 'b', "y"));
+        assert_eq!(cols.len(), 3);
+        assert_eq!(cols.row(1), Some((&2, &'b', &"y")));
+        assert_eq!(cols.row(3), None);
+        assert_eq!(
+            cols.columns(),
+            &(vec![1, 2, 3], vec!['a', 'b', 'c'], vec!["x", "y", "z"])
+        );
+
+        assert_eq!(cols.remove(0), (1, 'a', "x"));
+        assert_eq!(cols.pop(), Some((3, 'c', "z")));
+        assert_eq!(cols.pop(), Some((2, 'b', "y")));
+        assert_eq!(cols.pop(), None);
+    }
+
+    #[test]
+    fn rows_and_conversions() {
+        let cols: Columns<_> = vec![(1, 'a'), (2, 'b'), (3, 'c')].into_iter().collect();
+        assert_eq!(
+            cols.rows().rev().collect::<Vec<_>>(),
+            vec![(&3, &'c'), (&2, &'b'), (&1, &'a')]
+        );
+        assert_eq!(cols.rows().len(), 3);
+        assert_eq!(cols, Columns::from(vec![(1, 'a'), (2, 'b'), (3, 'c')]));
+        assert_eq!(Vec::from(cols), vec![(1, 'a'), (2, 'b'), (3, 'c')]);
+
+        let err = Columns::<(i32, char)>::try_from_columns((vec![1], vec![])).unwrap_err();
+        assert_eq!(err.lengths(), &[1, 0]);
+        let cols = Columns::<(i32, char)>::try_from_columns((vec![1], vec!['a'])).unwrap();
+        assert_eq!(cols.into_columns(), (vec![1], vec!['a']));
+    }
+
+    #[test]
+    #[should_panic]
+    fn insert_out_of_bounds() {
+        let mut cols = Columns::new();
+        cols.insert(1, (0, 0));
+    }
+}
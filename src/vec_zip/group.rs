@@ -0,0 +1,156 @@
+use std::collections::{BTreeMap, HashMap};
+use std::hash::Hash;
+
+// All grouping functions first split each item into the key and the rest of the row,
+// then collect the rows into a tuple of `Vec`s (using the `Extend` impl for tuples)
+
+fn group_hash<K: Hash + Eq, R, C: Default + Extend<R>>(
+    iter: impl Iterator<Item = (K, R)>,
+) -> HashMap<K, C> {
+    let mut map = HashMap::new();
+    for (k, row) in iter {
+        map.entry(k).or_insert_with(C::default).extend(Some(row));
+    }
+    map
+}
+
+fn group_btree<K: Ord, R, C: Default + Extend<R>>(
+    iter: impl Iterator<Item = (K, R)>,
+) -> BTreeMap<K, C> {
+    let mut map = BTreeMap::new();
+    for (k, row) in iter {
+        map.entry(k).or_insert_with(C::default).extend(Some(row));
+    }
+    map
+}
+
+// groups runs of consecutive items with equal keys
+struct SortedGroups<I: Iterator<Item = (K, R)>, K, R, C> {
+    iter: I,
+    // the first item of the next group
+    pending: Option<(K, R)>,
+    _marker: std::marker::PhantomData<C>,
+}
+
+impl<I: Iterator<Item = (K, R)>, K: PartialEq, R, C: Default + Extend<R>> Iterator
+    for SortedGroups<I, K, R, C>
+{
+    type Item = (K, C);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let (key, first) = self.pending.take().or_else(|| self.iter.next())?;
+        let mut cols = C::default();
+        cols.extend(Some(first));
+        for (k, row) in self.iter.by_ref() {
+            if k == key {
+                cols.extend(Some(row));
+            } else {
+                self.pending = Some((k, row));
+                break;
+            }
+        }
+        Some((key, cols))
+    }
+}
+
+fn group_sorted<K: PartialEq, R, C: Default + Extend<R>>(
+    iter: impl Iterator<Item = (K, R)>,
+) -> impl Iterator<Item = (K, C)> {
+    SortedGroups {
+        iter,
+        pending: None,
+        _marker: std::marker::PhantomData,
+    }
+}
+
+macro_rules! gen_group_unzip_fns {
+    (($hash:ident, $btree:ident, $sorted:ident) => $($binding:ident @ $param:ident: $t:ident),*) => {
+        /// Groups the rows by their first element (the key) and unzips the rest of each group into columns
+        ///
+        /// The order of the rows is preserved within each group.
+        #[allow(clippy::type_complexity)]
+        pub fn $hash<K: Hash + Eq, $($t),*>(
+            iter: impl IntoIterator<Item = (K, $($t),*)>,
+        ) -> HashMap<K, ($(Vec<$t>),*)> {
+            group_hash(iter.into_iter().map(|(k, $($binding),*)| (k, ($($binding),*))))
+        }
+
+        /// Like the `group_unzip*` functions, but returns a `BTreeMap`
+        /// (so the keys only need to be `Ord` and the groups are sorted by key)
+        #[allow(clippy::type_complexity)]
+        pub fn $btree<K: Ord, $($t),*>(
+            iter: impl IntoIterator<Item = (K, $($t),*)>,
+        ) -> BTreeMap<K, ($(Vec<$t>),*)> {
+            group_btree(iter.into_iter().map(|(k, $($binding),*)| (k, ($($binding),*))))
+        }
+
+        /// Like the `group_unzip*` functions, but for input that is sorted (or just grouped) by key
+        ///
+        /// Instead of hashing the keys, this lazily yields one group per run of consecutive rows with equal keys,
+        /// so only one group is in memory at a time.
+        /// If the input is not grouped by key, a key can appear in more than one group.
+        #[allow(clippy::type_complexity)]
+        pub fn $sorted<K: PartialEq, $($t),*>(
+            iter: impl IntoIterator<Item = (K, $($t),*)>,
+        ) -> impl Iterator<Item = (K, ($(Vec<$t>),*))> {
+            group_sorted(iter.into_iter().map(|(k, $($binding),*)| (k, ($($binding),*))))
+        }
+    };
+}
+
+gen_fns_accum! { gen_group_unzip_fns;
+    (group_unzip, group_unzip_btree, group_unzip_sorted) => t1 @ a: A, t2 @ b: B;
+    (group_unzip3, group_unzip_btree3, group_unzip_sorted3) => t3 @ c: C;
+    (group_unzip4, group_unzip_btree4, group_unzip_sorted4) => t4 @ d: D;
+    (group_unzip5, group_unzip_btree5, group_unzip_sorted5) => t5 @ e: E;
+    (group_unzip6, group_unzip_btree6, group_unzip_sorted6) => t6 @ f: F;
+    (group_unzip7, group_unzip_btree7, group_unzip_sorted7) => t7 @ g: G;
+    (group_unzip8, group_unzip_btree8, group_unzip_sorted8) => t8 @ h: H;
+    (group_unzip9, group_unzip_btree9, group_unzip_sorted9) => t9 @ i: I;
+    (group_unzip10, group_unzip_btree10, group_unzip_sorted10) => t10 @ j: J;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn grouping() {
+        let v = vec![("x", 1, 'a'), ("y", 2, 'b'), ("x", 3, 'c')];
+
+        let map = group_unzip(v.clone());
+        assert_eq!(map.len(), 2);
+        assert_eq!(map["x"], (vec![1, 3], vec!['a', 'c']));
+        assert_eq!(map["y"], (vec![2], vec!['b']));
+
+        let map = group_unzip_btree(v);
+        assert_eq!(
+            map.into_iter().collect::<Vec<_>>(),
+            vec![
+                ("x", (vec![1, 3], vec!['a', 'c'])),
+                ("y", (vec![2], vec!['b']))
+            ]
+        );
+    }
+
+    #[test]
+    fn sorted() {
+        let v = vec![
+            (1, 'a', 0.5, "p"),
+            (1, 'b', 1.5, "q"),
+            (2, 'c', 2.5, "r"),
+            (1, 'd', 3.5, "s"),
+        ];
+        let groups: Vec<_> = group_unzip_sorted3(v).collect();
+        assert_eq!(
+            groups,
+            vec![
+                (1, (vec!['a', 'b'], vec![0.5, 1.5], vec!["p", "q"])),
+                (2, (vec!['c'], vec![2.5], vec!["r"])),
+                (1, (vec!['d'], vec![3.5], vec!["s"]))
+            ]
+        );
+
+        assert_eq!(group_unzip_sorted(Vec::<(u8, u8, u8)>::new()).count(), 0);
+    }
+}
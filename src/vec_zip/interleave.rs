@@ -0,0 +1,209 @@
+use super::ZipLengthMismatch;
+use crate::tuple::HomogeneousTuple;
+use std::array;
+use std::error::Error;
+use std::fmt;
+
+/// What to do with data that doesn't make up a full frame
+/// (one item per channel) at the end of an interleaved buffer
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
+pub enum FramePolicy {
+    /// Return an error
+    Strict,
+    /// Drop the trailing partial frame
+    Truncate,
+}
+
+/// The error returned by [`deinterleave`](fn.deinterleave.html) with `FramePolicy::Strict`
+/// when the buffer ends with a partial frame
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
+pub struct PartialFrame {
+    len: usize,
+    channels: usize,
+}
+
+impl PartialFrame {
+    /// The length of the interleaved buffer
+    pub fn buffer_len(&self) -> usize {
+        self.len
+    }
+
+    /// The number of channels it was split into
+    pub fn channels(&self) -> usize {
+        self.channels
+    }
+
+    /// The number of items in the trailing partial frame
+    pub fn rest(&self) -> usize {
+        self.len % self.channels
+    }
+}
+
+impl fmt::Display for PartialFrame {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "a buffer of length {} doesn't split into whole frames of {} channels",
+            self.len, self.channels
+        )
+    }
+}
+
+impl Error for PartialFrame {}
+
+/// Splits a flat interleaved buffer (like `L R L R ...`) into `N` channels
+///
+/// This is the flat counterpart of [`unzip`](fn.unzip.html).
+/// If the buffer's length is not a multiple of `N`, `policy` decides what happens to the last, partial frame.
+///
+/// # Panics
+///
+/// Panics if `N` is 0
+///
+/// Example:
+/// ```
+/// # use tlib::vec_zip::{deinterleave, FramePolicy};
+/// let [l, r] = deinterleave(vec![1, 2, 3, 4, 5], FramePolicy::Truncate).unwrap();
+/// assert_eq!(l, vec![1, 3]);
+/// assert_eq!(r, vec![2, 4]);
+///
+/// let err = deinterleave::<_, 2>(vec![1, 2, 3, 4, 5], FramePolicy::Strict).unwrap_err();
+/// assert_eq!(err.rest(), 1);
+/// ```
+pub fn deinterleave<T, const N: usize>(
+    v: Vec<T>,
+    policy: FramePolicy,
+) -> Result<[Vec<T>; N], PartialFrame> {
+    assert!(N > 0, "tried to deinterleave into 0 channels");
+    let frames = v.len() / N;
+    let rest = v.len() % N;
+    if policy == FramePolicy::Strict && rest != 0 {
+        return Err(PartialFrame {
+            len: v.len(),
+            channels: N,
+        });
+    }
+
+    let mut channels: [Vec<T>; N] = array::from_fn(|_| Vec::with_capacity(frames));
+    for (i, x) in v.into_iter().take(frames * N).enumerate() {
+        channels[i % N].push(x);
+    }
+    Ok(channels)
+}
+
+/// Merges `N` channels into a flat interleaved buffer (like `L R L R ...`)
+///
+/// This is the flat counterpart of [`zip`](fn.zip.html).
+/// If the channels have different lengths, `policy` decides whether to return an error
+/// or to stop at the end of the shortest channel.
+///
+/// Example:
+/// ```
+/// # use tlib::vec_zip::{interleave, FramePolicy};
+/// let v = interleave([vec![1, 3], vec![2, 4, 6]], FramePolicy::Truncate).unwrap();
+/// assert_eq!(v, vec![1, 2, 3, 4]);
+/// ```
+pub fn interleave<T, const N: usize>(
+    channels: [Vec<T>; N],
+    policy: FramePolicy,
+) -> Result<Vec<T>, ZipLengthMismatch> {
+    let lengths = channels.each_ref().map(Vec::len);
+    if policy == FramePolicy::Strict {
+        ZipLengthMismatch::check(&lengths)?;
+    }
+    let frames = lengths.iter().copied().min().unwrap_or(0);
+
+    let mut iters = channels.map(Vec::into_iter);
+    let mut res = Vec::with_capacity(frames * N);
+    for _ in 0..frames {
+        for it in &mut iters {
+            // every channel has at least `frames` items
+            res.push(it.next().unwrap());
+        }
+    }
+    Ok(res)
+}
+
+/// Like [`deinterleave`](fn.deinterleave.html), but returns the channels as a tuple
+/// (which works for 1 to 12 channels)
+///
+/// Example:
+/// ```
+/// # use tlib::vec_zip::{deinterleave_tuple, FramePolicy};
+/// let (x, y, z) = deinterleave_tuple(vec![1, 2, 3, 4, 5, 6], FramePolicy::Strict).unwrap();
+/// assert_eq!((x, y, z), (vec![1, 4], vec![2, 5], vec![3, 6]));
+/// ```
+pub fn deinterleave_tuple<C, T, const N: usize>(
+    v: Vec<T>,
+    policy: FramePolicy,
+) -> Result<C, PartialFrame>
+where
+    C: HomogeneousTuple<Item = Vec<T>, Array = [Vec<T>; N]>,
+{
+    deinterleave(v, policy).map(C::from_array)
+}
+
+/// Like [`interleave`](fn.interleave.html), but takes the channels as a tuple
+/// (which works for 1 to 12 channels)
+pub fn interleave_tuple<C, T, const N: usize>(
+    channels: C,
+    policy: FramePolicy,
+) -> Result<Vec<T>, ZipLengthMismatch>
+where
+    C: HomogeneousTuple<Item = Vec<T>, Array = [Vec<T>; N]>,
+{
+    interleave(channels.to_array(), policy)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn roundtrip() {
+        let v: Vec<_> = (0..12).collect();
+        let [a, b, c] = deinterleave(v.clone(), FramePolicy::Strict).unwrap();
+        assert_eq!(a, vec![0, 3, 6, 9]);
+        assert_eq!(
+            interleave([a, b, c], FramePolicy::Strict).unwrap(),
+            v.clone()
+        );
+
+        let t: (Vec<_>, Vec<_>, Vec<_>, Vec<_>) =
+            deinterleave_tuple(v.clone(), FramePolicy::Strict).unwrap();
+        assert_eq!(t.3, vec![3, 7, 11]);
+        assert_eq!(interleave_tuple(t, FramePolicy::Strict).unwrap(), v);
+    }
+
+    #[test]
+    fn partial_frames() {
+        let v: Vec<_> = (0..7).collect();
+        let err = deinterleave::<_, 3>(v.clone(), FramePolicy::Strict).unwrap_err();
+        assert_eq!((err.buffer_len(), err.channels(), err.rest()), (7, 3, 1));
+        assert_eq!(
+            deinterleave(v, FramePolicy::Truncate).unwrap(),
+            [vec![0, 3], vec![1, 4], vec![2, 5]]
+        );
+
+        let channels = [vec![0, 2], vec![1]];
+        assert_eq!(
+            interleave(channels.clone(), FramePolicy::Strict)
+                .unwrap_err()
+                .lengths(),
+            &[2, 1]
+        );
+        assert_eq!(
+            interleave(channels, FramePolicy::Truncate).unwrap(),
+            vec![0, 1]
+        );
+
+        let empty: [Vec<i32>; 0] = [];
+        assert_eq!(interleave(empty, FramePolicy::Strict).unwrap(), vec![]);
+    }
+
+    #[test]
+    #[should_panic]
+    fn zero_channels() {
+        let _ = deinterleave::<_, 0>(vec![1], FramePolicy::Truncate);
+    }
+}
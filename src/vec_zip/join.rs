@@ -0,0 +1,304 @@
+use std::collections::{BTreeMap, HashMap};
+use std::hash::Hash;
+
+// All join functions work on any `IntoIterator<Item = (K, V)>`,
+// so they accept `HashMap`s, `BTreeMap`s and `Vec<(K, V)>`s alike.
+// There are three ways of finding the matching values for each key:
+// collecting them into a `BTreeMap` (so the output is sorted by key), into a `HashMap`,
+// or merging inputs that are already sorted by key without any map.
+// Each input fills its own slot of a tuple of `Vec`s, and every combination of the values
+// in those slots becomes one row (so duplicate keys give all matches, like in SQL).
+// All kinds of joins return the same shape of rows, `(K, (Option<A>, Option<B>, ...))`.
+//
+// `$pat` is the tuple of all bindings (as a single token tree),
+// so that it can be used inside the repetition over the inputs.
+// `req` slots need at least one value for the key to show up
+// (so they are always `Some`), `opt` slots are `None` if there is no value.
+
+// collects the values of every input into their slot of the group for their key
+macro_rules! fill_groups {
+    ($groups:ident, $pat:tt, $($binding:ident @ $param:ident),*) => {
+        $(
+            for (k, v) in $param {
+                #[allow(unused_variables)]
+                let $pat = $groups.entry(k).or_default();
+                $binding.push(v);
+            }
+        )*
+    };
+}
+
+// pushes one row for every combination of the values in the slots
+macro_rules! push_rows {
+    ($rows:ident, $k:ident, $($binding:ident [$mode:ident]),*) => {{
+        let total = 1 $(* push_rows!(@len $mode $binding))*;
+        for n in 0..total {
+            // the last slot changes fastest, like in nested loops
+            let mut stride = total;
+            $rows.push(($k.clone(), ($({
+                let len = push_rows!(@len $mode $binding);
+                stride /= len;
+                push_rows!(@get $mode $binding, n / stride % len)
+            }),*)));
+        }
+    }};
+    (@len req $binding:ident) => {
+        $binding.len()
+    };
+    (@len opt $binding:ident) => {
+        $binding.len().max(1)
+    };
+    (@get req $binding:ident, $i:expr) => {
+        Some($binding[$i].clone())
+    };
+    (@get opt $binding:ident, $i:expr) => {
+        $binding.get($i).cloned()
+    };
+}
+
+macro_rules! gen_join_fns {
+    ((outer: $btree:ident, $hash:ident, $sorted:ident) => $($binding:ident @ $param:ident: $t:ident),*) => {
+        gen_join_fns!(@impl [$btree, $hash, $sorted]
+            "Joins several keyed collections by key, keeping all keys that appear in any of them (a full outer join)",
+            "A value is `None` if its input has no value for the key.",
+            ($($binding),*) => $($binding @ $param: $t [opt]),*);
+    };
+    ((left: $btree:ident, $hash:ident, $sorted:ident) => $b1:ident @ $p1:ident: $t1:ident $(, $binding:ident @ $param:ident: $t:ident)*) => {
+        gen_join_fns!(@impl [$btree, $hash, $sorted]
+            "Joins several keyed collections by key, keeping exactly the keys of the first one (a left join)",
+            "The first value is always `Some`, the others are `None` if their input has no value for the key.",
+            ($b1 $(, $binding)*) => $b1 @ $p1: $t1 [req] $(, $binding @ $param: $t [opt])*);
+    };
+    ((inner: $btree:ident, $hash:ident, $sorted:ident) => $($binding:ident @ $param:ident: $t:ident),*) => {
+        gen_join_fns!(@impl [$btree, $hash, $sorted]
+            "Joins several keyed collections by key, keeping only the keys that appear in all of them (an inner join)",
+            "All values are always `Some` (they are only wrapped to give every join the same row shape).",
+            ($($binding),*) => $($binding @ $param: $t [req]),*);
+    };
+    (@impl [$btree:ident, $hash:ident, $sorted:ident] $doc:literal, $some:literal, $pat:tt => $($binding:ident @ $param:ident: $t:ident [$mode:ident]),*) => {
+        #[doc = $doc]
+        ///
+        /// The inputs can be anything that iterates over `(key, value)` pairs
+        /// (e.g. `HashMap`, `BTreeMap` or `Vec<(K, V)>`).
+        /// Every row holds one `Option` per input, in argument order.
+        #[doc = $some]
+        ///
+        /// If a key appears more than once, there is one row for every combination of its values
+        /// (like in SQL), so the values are cloned.
+        ///
+        /// The result is sorted by key.
+        #[allow(clippy::too_many_arguments, clippy::type_complexity)]
+        pub fn $btree<K: Ord + Clone, $($t: Clone),*>(
+            $($param: impl IntoIterator<Item = (K, $t)>),*
+        ) -> Vec<(K, ($(Option<$t>),*))> {
+            let mut groups: BTreeMap<K, ($(Vec<$t>),*)> = BTreeMap::new();
+            fill_groups!(groups, $pat, $($binding @ $param),*);
+            let mut rows = Vec::new();
+            for (k, $pat) in groups {
+                push_rows!(rows, k, $($binding [$mode]),*);
+            }
+            rows
+        }
+
+        #[doc = concat!("Like [`", stringify!($btree), "`](fn.", stringify!($btree), ".html), but hashes the keys instead of sorting them")]
+        ///
+        /// The result is in no particular order.
+        #[allow(clippy::too_many_arguments, clippy::type_complexity)]
+        pub fn $hash<K: Hash + Eq + Clone, $($t: Clone),*>(
+            $($param: impl IntoIterator<Item = (K, $t)>),*
+        ) -> Vec<(K, ($(Option<$t>),*))> {
+            let mut groups: HashMap<K, ($(Vec<$t>),*)> = HashMap::new();
+            fill_groups!(groups, $pat, $($binding @ $param),*);
+            let mut rows = Vec::new();
+            for (k, $pat) in groups {
+                push_rows!(rows, k, $($binding [$mode]),*);
+            }
+            rows
+        }
+
+        #[doc = concat!("Like [`", stringify!($btree), "`](fn.", stringify!($btree), ".html), but for inputs that are already sorted by key")]
+        ///
+        /// The inputs are merged in a single pass, without building a map.
+        /// If an input is not sorted by key, the result is unspecified
+        /// (it is neither sorted nor are all matches found, but it doesn't panic).
+        #[allow(clippy::too_many_arguments, clippy::type_complexity)]
+        pub fn $sorted<K: Ord + Clone, $($t: Clone),*>(
+            $($param: impl IntoIterator<Item = (K, $t)>),*
+        ) -> Vec<(K, ($(Option<$t>),*))> {
+            $(
+                let mut $param = $param.into_iter().peekable();
+            )*
+            let mut rows = Vec::new();
+            loop {
+                // the smallest key at the front of any input
+                let mut min: Option<&K> = None;
+                $(
+                    if let Some((k, _)) = $param.peek() {
+                        if min.is_none_or(|m| k < m) {
+                            min = Some(k);
+                        }
+                    }
+                )*
+                let key = match min {
+                    Some(k) => k.clone(),
+                    None => break,
+                };
+                $(
+                    let mut $binding = Vec::new();
+                    while let Some((_, v)) = $param.next_if(|(k, _)| *k == key) {
+                        $binding.push(v);
+                    }
+                )*
+                push_rows!(rows, key, $($binding [$mode]),*);
+            }
+            rows
+        }
+    };
+}
+
+gen_fns_accum! { gen_join_fns;
+    (outer: join_outer, join_outer_hash, join_outer_sorted) => t1 @ a: A, t2 @ b: B;
+    (outer: join_outer3, join_outer_hash3, join_outer_sorted3) => t3 @ c: C;
+    (outer: join_outer4, join_outer_hash4, join_outer_sorted4) => t4 @ d: D;
+    (outer: join_outer5, join_outer_hash5, join_outer_sorted5) => t5 @ e: E;
+    (outer: join_outer6, join_outer_hash6, join_outer_sorted6) => t6 @ f: F;
+    (outer: join_outer7, join_outer_hash7, join_outer_sorted7) => t7 @ g: G;
+    (outer: join_outer8, join_outer_hash8, join_outer_sorted8) => t8 @ h: H;
+    (outer: join_outer9, join_outer_hash9, join_outer_sorted9) => t9 @ i: I;
+    (outer: join_outer10, join_outer_hash10, join_outer_sorted10) => t10 @ j: J;
+}
+
+gen_fns_accum! { gen_join_fns;
+    (left: join_left, join_left_hash, join_left_sorted) => t1 @ a: A, t2 @ b: B;
+    (left: join_left3, join_left_hash3, join_left_sorted3) => t3 @ c: C;
+    (left: join_left4, join_left_hash4, join_left_sorted4) => t4 @ d: D;
+    (left: join_left5, join_left_hash5, join_left_sorted5) => t5 @ e: E;
+    (left: join_left6, join_left_hash6, join_left_sorted6) => t6 @ f: F;
+    (left: join_left7, join_left_hash7, join_left_sorted7) => t7 @ g: G;
+    (left: join_left8, join_left_hash8, join_left_sorted8) => t8 @ h: H;
+    (left: join_left9, join_left_hash9, join_left_sorted9) => t9 @ i: I;
+    (left: join_left10, join_left_hash10, join_left_sorted10) => t10 @ j: J;
+}
+
+gen_fns_accum! { gen_join_fns;
+    (inner: join_inner, join_inner_hash, join_inner_sorted) => t1 @ a: A, t2 @ b: B;
+    (inner: join_inner3, join_inner_hash3, join_inner_sorted3) => t3 @ c: C;
+    (inner: join_inner4, join_inner_hash4, join_inner_sorted4) => t4 @ d: D;
+    (inner: join_inner5, join_inner_hash5, join_inner_sorted5) => t5 @ e: E;
+    (inner: join_inner6, join_inner_hash6, join_inner_sorted6) => t6 @ f: F;
+    (inner: join_inner7, join_inner_hash7, join_inner_sorted7) => t7 @ g: G;
+    (inner: join_inner8, join_inner_hash8, join_inner_sorted8) => t8 @ h: H;
+    (inner: join_inner9, join_inner_hash9, join_inner_sorted9) => t9 @ i: I;
+    (inner: join_inner10, join_inner_hash10, join_inner_sorted10) => t10 @ j: J;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn join_two() {
+        let a: HashMap<_, _> = vec![(1, 'a'), (2, 'b'), (4, 'd')].into_iter().collect();
+        let b = vec![(2, "two"), (3, "three"), (4, "four")];
+
+        let outer = vec![
+            (1, (Some('a'), None)),
+            (2, (Some('b'), Some("two"))),
+            (3, (None, Some("three"))),
+            (4, (Some('d'), Some("four"))),
+        ];
+        assert_eq!(join_outer(a.clone(), b.clone()), outer);
+        let mut hashed = join_outer_hash(a.clone(), b.clone());
+        hashed.sort_by_key(|row| row.0);
+        assert_eq!(hashed, outer);
+        let mut sorted_a: Vec<_> = a.clone().into_iter().collect();
+        sorted_a.sort();
+        assert_eq!(join_outer_sorted(sorted_a.clone(), b.clone()), outer);
+
+        let left = vec![
+            (1, (Some('a'), None)),
+            (2, (Some('b'), Some("two"))),
+            (4, (Some('d'), Some("four"))),
+        ];
+        assert_eq!(join_left(a.clone(), b.clone()), left);
+        assert_eq!(join_left_sorted(sorted_a.clone(), b.clone()), left);
+
+        let inner = vec![
+            (2, (Some('b'), Some("two"))),
+            (4, (Some('d'), Some("four"))),
+        ];
+        assert_eq!(join_inner(a.clone(), b.clone()), inner);
+        let mut hashed = join_inner_hash(a, b.clone());
+        hashed.sort_by_key(|row| row.0);
+        assert_eq!(hashed, inner);
+        assert_eq!(join_inner_sorted(sorted_a, b), inner);
+    }
+
+    #[test]
+    fn join_three() {
+        let a: BTreeMap<_, _> = vec![(1, 'a'), (2, 'b')].into_iter().collect();
+        let b = vec![(2, 2.0), (3, 3.0)];
+        let c = vec![(2, "x"), (1, "y"), (2, "z")];
+
+        // the duplicate key in `c` gives one row per value
+        assert_eq!(
+            join_outer3(a.clone(), b.clone(), c.clone()),
+            vec![
+                (1, (Some('a'), None, Some("y"))),
+                (2, (Some('b'), Some(2.0), Some("x"))),
+                (2, (Some('b'), Some(2.0), Some("z"))),
+                (3, (None, Some(3.0), None))
+            ]
+        );
+        assert_eq!(
+            join_left3(a.clone(), b.clone(), c.clone()),
+            vec![
+                (1, (Some('a'), None, Some("y"))),
+                (2, (Some('b'), Some(2.0), Some("x"))),
+                (2, (Some('b'), Some(2.0), Some("z")))
+            ]
+        );
+        assert_eq!(
+            join_inner3(a.clone(), b.clone(), c.clone()),
+            vec![
+                (2, (Some('b'), Some(2.0), Some("x"))),
+                (2, (Some('b'), Some(2.0), Some("z")))
+            ]
+        );
+
+        let mut sorted_c = c;
+        sorted_c.sort_by_key(|row| row.0);
+        assert_eq!(
+            join_inner_sorted3(a, b, sorted_c),
+            vec![
+                (2, (Some('b'), Some(2.0), Some("x"))),
+                (2, (Some('b'), Some(2.0), Some("z")))
+            ]
+        );
+    }
+
+    #[test]
+    fn all_matches() {
+        let a = vec![(1, 'a'), (1, 'b')];
+        let b = vec![(1, 10), (1, 20), (2, 30)];
+        let expected = vec![
+            (1, (Some('a'), Some(10))),
+            (1, (Some('a'), Some(20))),
+            (1, (Some('b'), Some(10))),
+            (1, (Some('b'), Some(20))),
+        ];
+        assert_eq!(join_inner(a.clone(), b.clone()), expected);
+        assert_eq!(join_inner_hash(a.clone(), b.clone()), expected);
+        assert_eq!(join_inner_sorted(a.clone(), b.clone()), expected);
+        assert_eq!(
+            join_left(b.clone(), a.clone()),
+            vec![
+                (1, (Some(10), Some('a'))),
+                (1, (Some(10), Some('b'))),
+                (1, (Some(20), Some('a'))),
+                (1, (Some(20), Some('b'))),
+                (2, (Some(30), None))
+            ]
+        );
+    }
+}
@@ -0,0 +1,212 @@
+// Functions for reordering several parallel `Vec`s (columns) in lockstep
+
+// returns the common length
+fn assert_same_len(lengths: &[usize]) -> usize {
+    if lengths.windows(2).any(|w| w[0] != w[1]) {
+        panic!("columns have different lengths: {:?}", lengths);
+    }
+    lengths[0]
+}
+
+/// Returns the permutation that sorts `v` by the key `f` (stably)
+///
+/// The permutation maps new indices to old indices,
+/// so it can be used with [`apply_permutation`](fn.apply_permutation.html)
+pub fn sort_permutation_by_key<T, K: Ord, F: FnMut(&T) -> K>(v: &[T], mut f: F) -> Vec<usize> {
+    let mut perm: Vec<usize> = (0..v.len()).collect();
+    perm.sort_by_key(|&i| f(&v[i]));
+    perm
+}
+
+/// Reorders `v` in place, so that the element at index `i` afterwards is the one that was at `perm[i]` before
+///
+/// This needs `O(v.len())` extra memory for bookkeeping (but doesn't clone any elements)
+///
+/// # Panics
+///
+/// Panics if `perm` is not a permutation of `0..v.len()`
+pub fn apply_permutation<T>(v: &mut [T], perm: &[usize]) {
+    assert_eq!(
+        v.len(),
+        perm.len(),
+        "the permutation has a different length than the slice"
+    );
+    let mut done = vec![false; v.len()];
+    for start in 0..v.len() {
+        if done[start] {
+            continue;
+        }
+        done[start] = true;
+        // walk the cycle containing `start`, moving each element into place
+        let mut curr = start;
+        loop {
+            let next = perm[curr];
+            if next == start {
+                break;
+            }
+            assert!(!done[next], "`perm` is not a permutation");
+            v.swap(curr, next);
+            done[next] = true;
+            curr = next;
+        }
+    }
+}
+
+// keeps exactly the elements where `keep` is `true`
+fn retain_mask<T>(v: &mut Vec<T>, keep: &[bool]) {
+    let mut keep = keep.iter();
+    v.retain(|_| *keep.next().unwrap());
+}
+
+macro_rules! gen_sort_by_key_lockstep_fn {
+    ($fname:ident => $($binding:ident @ $param:ident: $t:ident),*) => {
+        /// Sorts several columns in lockstep (stably), by a key computed from each row (by `key`).
+        ///
+        /// The permutation is only computed once and then applied to each column in place,
+        /// without zipping the columns.
+        ///
+        /// # Panics
+        ///
+        /// Panics if the columns have different lengths
+        #[allow(clippy::too_many_arguments)]
+        pub fn $fname<$($t,)* K: Ord, Func: FnMut(($(&$t),*)) -> K>($($param: &mut [$t],)* mut key: Func) {
+            let len = assert_same_len(&[$($param.len()),*]);
+            let mut perm: Vec<usize> = (0..len).collect();
+            perm.sort_by_key(|&i| key(($(&$param[i]),*)));
+            $(
+                apply_permutation($param, &perm);
+            )*
+        }
+    };
+}
+
+macro_rules! gen_dedup_lockstep_fn {
+    ($fname:ident => $($binding:ident @ $param:ident: $t:ident),*) => {
+        /// Removes consecutive repeated rows from several columns in lockstep.
+        ///
+        /// Two rows count as equal if all of their elements are equal.
+        ///
+        /// # Panics
+        ///
+        /// Panics if the columns have different lengths
+        #[allow(clippy::too_many_arguments)]
+        pub fn $fname<$($t: PartialEq),*>($($param: &mut Vec<$t>),*) {
+            let len = assert_same_len(&[$($param.len()),*]);
+            let keep: Vec<bool> = (0..len)
+                .map(|i| i == 0 || $($param[i] != $param[i - 1])||*)
+                .collect();
+            $(
+                retain_mask($param, &keep);
+            )*
+        }
+    };
+}
+
+macro_rules! gen_retain_lockstep_fn {
+    ($fname:ident => $($binding:ident @ $param:ident: $t:ident),*) => {
+        /// Retains only the rows for which `pred` returns `true`, in several columns in lockstep.
+        ///
+        /// # Panics
+        ///
+        /// Panics if the columns have different lengths
+        #[allow(clippy::too_many_arguments)]
+        pub fn $fname<$($t,)* Func: FnMut(($(&$t),*)) -> bool>($($param: &mut Vec<$t>,)* mut pred: Func) {
+            let len = assert_same_len(&[$($param.len()),*]);
+            let keep: Vec<bool> = (0..len).map(|i| pred(($(&$param[i]),*))).collect();
+            $(
+                retain_mask($param, &keep);
+            )*
+        }
+    };
+}
+
+gen_fns_accum! { gen_sort_by_key_lockstep_fn;
+    sort_by_key_lockstep => t1 @ a: A, t2 @ b: B;
+    sort_by_key_lockstep3 => t3 @ c: C;
+    sort_by_key_lockstep4 => t4 @ d: D;
+    sort_by_key_lockstep5 => t5 @ e: E;
+    sort_by_key_lockstep6 => t6 @ f: F;
+    sort_by_key_lockstep7 => t7 @ g: G;
+    sort_by_key_lockstep8 => t8 @ h: H;
+    sort_by_key_lockstep9 => t9 @ i: I;
+    sort_by_key_lockstep10 => t10 @ j: J;
+}
+
+gen_fns_accum! { gen_dedup_lockstep_fn;
+    dedup_lockstep => t1 @ a: A, t2 @ b: B;
+    dedup_lockstep3 => t3 @ c: C;
+    dedup_lockstep4 => t4 @ d: D;
+    dedup_lockstep5 => t5 @ e: E;
+    dedup_lockstep6 => t6 @ f: F;
+    dedup_lockstep7 => t7 @ g: G;
+    dedup_lockstep8 => t8 @ h: H;
+    dedup_lockstep9 => t9 @ i: I;
+    dedup_lockstep10 => t10 @ j: J;
+}
+
+gen_fns_accum! { gen_retain_lockstep_fn;
+    retain_lockstep => t1 @ a: A, t2 @ b: B;
+    retain_lockstep3 => t3 @ c: C;
+    retain_lockstep4 => t4 @ d: D;
+    retain_lockstep5 => t5 @ e: E;
+    retain_lockstep6 => t6 @ f: F;
+    retain_lockstep7 => t7 @ g: G;
+    retain_lockstep8 => t8 @ h: H;
+    retain_lockstep9 => t9 @ i: I;
+    retain_lockstep10 => t10 @ j: J;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn permutation() {
+        let mut v = vec!['c', 'a', 'd', 'b', 'e'];
+        let perm = sort_permutation_by_key(&v, |&c| c);
+        assert_eq!(perm, vec![1, 3, 0, 2, 4]);
+        apply_permutation(&mut v, &perm);
+        assert_eq!(v, vec!['a', 'b', 'c', 'd', 'e']);
+    }
+
+    #[test]
+    #[should_panic]
+    fn invalid_permutation() {
+        apply_permutation(&mut [0, 1, 2], &[1, 1, 0]);
+    }
+
+    #[test]
+    fn sort_lockstep() {
+        let mut keys = vec![3, 1, 2, 1];
+        let mut names = vec!["c", "a", "b", "a2"];
+        let mut values = vec![3.0, 1.0, 2.0, 1.5];
+        sort_by_key_lockstep3(&mut keys, &mut names, &mut values, |(k, _, _)| *k);
+        assert_eq!(keys, vec![1, 1, 2, 3]);
+        assert_eq!(names, vec!["a", "a2", "b", "c"]);
+        assert_eq!(values, vec![1.0, 1.5, 2.0, 3.0]);
+
+        sort_by_key_lockstep(&mut names, &mut values, |(_, v)| -(*v as i32));
+        assert_eq!(names, vec!["c", "b", "a", "a2"]);
+    }
+
+    #[test]
+    fn dedup_and_retain() {
+        let mut a = vec![1, 1, 1, 2, 2, 1];
+        let mut b = vec!['a', 'a', 'b', 'b', 'b', 'b'];
+        dedup_lockstep(&mut a, &mut b);
+        assert_eq!(a, vec![1, 1, 2, 1]);
+        assert_eq!(b, vec!['a', 'b', 'b', 'b']);
+
+        let mut c = vec![0, 1, 2, 3];
+        retain_lockstep3(&mut a, &mut b, &mut c, |(&a, _, &c)| a + c < 3);
+        assert_eq!(a, vec![1, 1]);
+        assert_eq!(b, vec!['a', 'b']);
+        assert_eq!(c, vec![0, 1]);
+    }
+
+    #[test]
+    #[should_panic]
+    fn different_lengths() {
+        retain_lockstep(&mut vec![0], &mut vec![0, 1], |_| true);
+    }
+}
@@ -0,0 +1,141 @@
+// Parallel versions of the zip and unzip functions, using rayon
+use rayon::prelude::*;
+
+/// Like [`zip`](fn.zip.html), but distributes the work across threads using rayon
+///
+/// The order of the elements is preserved
+pub fn par_zip<A: Send, B: Send>(a: Vec<A>, b: Vec<B>) -> Vec<(A, B)> {
+    a.into_par_iter().zip(b).collect()
+}
+
+/// Like [`unzip`](fn.unzip.html), but distributes the work across threads using rayon
+///
+/// The order of the elements is preserved
+pub fn par_unzip<A: Send, B: Send>(v: Vec<(A, B)>) -> (Vec<A>, Vec<B>) {
+    v.into_par_iter().unzip()
+}
+
+macro_rules! gen_par_zip_fn {
+    ($fname:ident => $($binding:ident @ $param:ident: $t:ident),*) => {
+        /// Like [`par_zip`](fn.par_zip.html) but with more `Vec`s
+        #[allow(clippy::too_many_arguments)]
+        pub fn $fname<$($t: Send),*>($($param: Vec<$t>),*) -> Vec<($($t),*)> {
+            ($($param),*).into_par_iter().collect()
+        }
+    };
+}
+
+// concatenates the chunks of a column, reusing the first one's allocation
+fn concat<T>(chunks: Vec<Vec<T>>) -> Vec<T> {
+    let len: usize = chunks.iter().map(Vec::len).sum();
+    let mut chunks = chunks.into_iter();
+    let mut res = chunks.next().unwrap_or_default();
+    res.reserve_exact(len - res.len());
+    for chunk in chunks {
+        res.extend(chunk);
+    }
+    res
+}
+
+macro_rules! gen_par_unzip_fn {
+    ($fname:ident => $($binding:ident @ $param:ident: $t:ident),*) => {
+        /// Like [`par_unzip`](fn.par_unzip.html) but with more `Vec`s
+        #[allow(clippy::type_complexity)]
+        pub fn $fname<$($t: Send),*>(v: Vec<($($t),*)>) -> ($(Vec<$t>),*) {
+            // the rows are split into one chunk per thread, and every chunk is unzipped sequentially
+            // (rayon's `unzip` only works on pairs, and nesting it is slower than this)
+            let threads = rayon::current_num_threads();
+            let chunk_len = v.len().div_ceil(threads).max(1);
+            let chunks: Vec<Box<($(Vec<$t>),*)>> = v
+                .into_par_iter()
+                // the columns are boxed so that they aren't moved around for every row
+                .fold_chunks(
+                    chunk_len,
+                    || Box::new(($(Vec::<$t>::with_capacity(chunk_len)),*)),
+                    |mut cols, ($($binding),*)| {
+                        let ($($param),*) = &mut *cols;
+                        $(
+                            $param.push($binding);
+                        )*
+                        cols
+                    },
+                )
+                .collect();
+
+            // then the chunks of every column are concatenated, one job per column
+            $(
+                let mut $param = Vec::with_capacity(chunks.len());
+            )*
+            for chunk in chunks {
+                let ($($binding),*) = *chunk;
+                $(
+                    $param.push($binding);
+                )*
+            }
+            $(
+                let mut $binding = Vec::new();
+            )*
+            rayon::scope(|s| {
+                $(
+                    let col = &mut $binding;
+                    s.spawn(move |_| *col = concat($param));
+                )*
+            });
+            ($($binding),*)
+        }
+    };
+}
+
+gen_fns_accum! { gen_par_zip_fn;
+    par_zip3 => t1 @ a: A, t2 @ b: B, t3 @ c: C;
+    par_zip4 => t4 @ d: D;
+    par_zip5 => t5 @ e: E;
+    par_zip6 => t6 @ f: F;
+    par_zip7 => t7 @ g: G;
+    par_zip8 => t8 @ h: H;
+    par_zip9 => t9 @ i: I;
+    par_zip10 => t10 @ j: J;
+}
+
+gen_fns_accum! { gen_par_unzip_fn;
+    par_unzip3 => t1 @ a: A, t2 @ b: B, t3 @ c: C;
+    par_unzip4 => t4 @ d: D;
+    par_unzip5 => t5 @ e: E;
+    par_unzip6 => t6 @ f: F;
+    par_unzip7 => t7 @ g: G;
+    par_unzip8 => t8 @ h: H;
+    par_unzip9 => t9 @ i: I;
+    par_unzip10 => t10 @ j: J;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::*;
+    use super::*;
+
+    #[test]
+    fn same_as_sequential() {
+        let a: Vec<u32> = (0..10_000).collect();
+        let b: Vec<String> = a.iter().map(|x| x.to_string()).collect();
+        let c: Vec<f64> = a.iter().map(|&x| f64::from(x) / 2.0).collect();
+
+        assert_eq!(
+            par_zip(a.clone(), b[..5000].to_vec()),
+            zip(a.clone(), b[..5000].to_vec())
+        );
+
+        let zipped = par_zip3(a.clone(), b.clone(), c.clone());
+        assert_eq!(zipped, zip3(a.clone(), b.clone(), c.clone()));
+        // more threads than cores, so that the rows are split into several chunks
+        let pool = rayon::ThreadPoolBuilder::new()
+            .num_threads(4)
+            .build()
+            .unwrap();
+        assert_eq!(
+            pool.install(|| par_unzip3(zipped.clone())),
+            (a.clone(), b.clone(), c.clone())
+        );
+        assert_eq!(par_unzip3(zipped), (a.clone(), b.clone(), c));
+        assert_eq!(par_unzip(zip(a.clone(), b.clone())), (a, b));
+    }
+}
@@ -0,0 +1,110 @@
+// Splitting a sequence of "one of several types" items into one collection per type,
+// the sum-type counterpart of `unzip_into`
+
+/// A value that is one of two types, used by [`partition_unzip`](fn.partition_unzip.html)
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
+pub enum Either<L, R> {
+    #[allow(missing_docs)]
+    Left(L),
+    #[allow(missing_docs)]
+    Right(R),
+}
+
+/// Sends each item into one of two collections, depending on whether it is `Left` or `Right`
+///
+/// The collections can be anything that implements `Default` and `Extend`.
+/// The order of the items is preserved within each collection.
+///
+/// Example:
+/// ```
+/// # use tlib::vec_zip::{partition_unzip, Either};
+/// let items = ["1", "x", "2"].iter().map(|s| match s.parse::<u8>() {
+///     Ok(n) => Either::Left(n),
+///     Err(_) => Either::Right(*s),
+/// });
+/// let (nums, other): (Vec<_>, String) = partition_unzip(items);
+/// assert_eq!(nums, vec![1, 2]);
+/// assert_eq!(other, "x");
+/// ```
+pub fn partition_unzip<CL, CR, L, R>(iter: impl IntoIterator<Item = Either<L, R>>) -> (CL, CR)
+where
+    CL: Default + Extend<L>,
+    CR: Default + Extend<R>,
+{
+    let mut left = CL::default();
+    let mut right = CR::default();
+    for item in iter {
+        match item {
+            Either::Left(l) => left.extend(Some(l)),
+            Either::Right(r) => right.extend(Some(r)),
+        }
+    }
+    (left, right)
+}
+
+// `$coll` are the type parameters for the collections
+macro_rules! gen_partition_fn {
+    (($fname:ident, $enum:ident) => $($coll:ident @ $param:ident: $t:ident),*) => {
+        /// A value that is one of several types, used by the `partition_unzip*` functions
+        /// (the variants are named like the type parameters)
+        #[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
+        pub enum $enum<$($t),*> {
+            $(
+                #[allow(missing_docs)]
+                $t($t),
+            )*
+        }
+
+        /// Like [`partition_unzip`](fn.partition_unzip.html) but with more collections
+        pub fn $fname<$($coll: Default + Extend<$t>,)* $($t),*>(
+            iter: impl IntoIterator<Item = $enum<$($t),*>>,
+        ) -> ($($coll),*) {
+            $(
+                let mut $param = $coll::default();
+            )*
+            for item in iter {
+                match item {
+                    $(
+                        $enum::$t(x) => $param.extend(Some(x)),
+                    )*
+                }
+            }
+            ($($param),*)
+        }
+    };
+}
+
+gen_fns_accum! { gen_partition_fn;
+    (partition_unzip3, OneOf3) => CA @ a: A, CB @ b: B, CC @ c: C;
+    (partition_unzip4, OneOf4) => CD @ d: D;
+    (partition_unzip5, OneOf5) => CE @ e: E;
+    (partition_unzip6, OneOf6) => CF @ f: F;
+    (partition_unzip7, OneOf7) => CG @ g: G;
+    (partition_unzip8, OneOf8) => CH @ h: H;
+    (partition_unzip9, OneOf9) => CI @ i: I;
+    (partition_unzip10, OneOf10) => CJ @ j: J;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::BTreeSet;
+
+    #[test]
+    fn partition() {
+        let items = vec![
+            OneOf3::A(1),
+            OneOf3::C("x"),
+            OneOf3::B('b'),
+            OneOf3::A(1),
+            OneOf3::C("y"),
+        ];
+        let (a, b, c): (BTreeSet<_>, String, Vec<_>) = partition_unzip3(items);
+        assert_eq!(a.into_iter().collect::<Vec<_>>(), vec![1]);
+        assert_eq!(b, "b");
+        assert_eq!(c, vec!["x", "y"]);
+
+        let (l, r): (Vec<i32>, Vec<()>) = partition_unzip(Vec::<Either<i32, ()>>::new());
+        assert!(l.is_empty() && r.is_empty());
+    }
+}
@@ -0,0 +1,116 @@
+use super::ZipLengthMismatch;
+
+// turns the rows into columns, taking `ncols` items from every row
+// and calling `fill` where a row is too short
+fn transpose_impl<T, F: FnMut() -> T>(v: Vec<Vec<T>>, ncols: usize, mut fill: F) -> Vec<Vec<T>> {
+    let nrows = v.len();
+    let mut rows: Vec<_> = v.into_iter().map(Vec::into_iter).collect();
+    (0..ncols)
+        .map(|_| {
+            let mut col = Vec::with_capacity(nrows);
+            for row in &mut rows {
+                col.push(row.next().unwrap_or_else(&mut fill));
+            }
+            col
+        })
+        .collect()
+}
+
+/// Transposes a `Vec` of rows into a `Vec` of columns (and vice versa)
+///
+/// This is the dynamic counterpart to [`zip`](fn.zip.html)/[`unzip`](fn.unzip.html):
+/// if the rows have different lengths, all of them are truncated to the shortest one.
+///
+/// Example:
+/// ```
+/// # use tlib::vec_zip::transpose;
+/// let v = vec![vec![1, 2, 3], vec![4, 5]];
+/// assert_eq!(transpose(v), vec![vec![1, 4], vec![2, 5]]);
+/// ```
+pub fn transpose<T>(v: Vec<Vec<T>>) -> Vec<Vec<T>> {
+    let ncols = v.iter().map(Vec::len).min().unwrap_or(0);
+    // never called, since no row is shorter than `ncols`
+    transpose_impl(v, ncols, || unreachable!())
+}
+
+/// Like [`transpose`](fn.transpose.html), but returns an error instead of truncating
+/// when the rows don't all have the same length
+pub fn try_transpose<T>(v: Vec<Vec<T>>) -> Result<Vec<Vec<T>>, ZipLengthMismatch> {
+    ZipLengthMismatch::check(&v.iter().map(Vec::len).collect::<Vec<_>>())?;
+    Ok(transpose(v))
+}
+
+/// Like [`transpose`](fn.transpose.html), but instead of truncating,
+/// pads the shorter rows with clones of `fill` to the length of the longest one
+pub fn transpose_pad<T: Clone>(v: Vec<Vec<T>>, fill: T) -> Vec<Vec<T>> {
+    let ncols = v.iter().map(Vec::len).max().unwrap_or(0);
+    transpose_impl(v, ncols, || fill.clone())
+}
+
+/// Transposes a square matrix (given as a slice of rows) in place
+///
+/// # Panics
+///
+/// Panics if any row's length is different from the number of rows
+pub fn transpose_in_place<T>(v: &mut [Vec<T>]) {
+    let n = v.len();
+    if let Some(row) = v.iter().find(|row| row.len() != n) {
+        panic!(
+            "tried to transpose a non-square matrix in place ({} rows, but found a row of length {})",
+            n,
+            row.len()
+        );
+    }
+    for i in 0..n {
+        let (upper, lower) = v.split_at_mut(i + 1);
+        let row_i = &mut upper[i];
+        for (k, row_j) in lower.iter_mut().enumerate() {
+            std::mem::swap(&mut row_i[i + 1 + k], &mut row_j[i]);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn ragged() {
+        let v = vec![vec![1, 2, 3], vec![4], vec![5, 6]];
+        assert_eq!(transpose(v.clone()), vec![vec![1, 4, 5]]);
+        assert_eq!(
+            transpose_pad(v.clone(), 0),
+            vec![vec![1, 4, 5], vec![2, 0, 6], vec![3, 0, 0]]
+        );
+        assert_eq!(try_transpose(v).unwrap_err().lengths(), &[3, 1, 2]);
+    }
+
+    #[test]
+    fn rectangular() {
+        let v = vec![vec![1, 2, 3], vec![4, 5, 6]];
+        let t = vec![vec![1, 4], vec![2, 5], vec![3, 6]];
+        assert_eq!(try_transpose(v.clone()), Ok(t.clone()));
+        assert_eq!(transpose_pad(v.clone(), 0), t);
+        assert_eq!(transpose(t), v);
+
+        assert_eq!(transpose(Vec::<Vec<i32>>::new()), Vec::<Vec<i32>>::new());
+        assert_eq!(transpose(vec![Vec::<i32>::new()]), Vec::<Vec<i32>>::new());
+    }
+
+    #[test]
+    fn in_place() {
+        let mut v = vec![vec![1, 2, 3], vec![4, 5, 6], vec![7, 8, 9]];
+        transpose_in_place(&mut v);
+        assert_eq!(v, vec![vec![1, 4, 7], vec![2, 5, 8], vec![3, 6, 9]]);
+
+        let mut v: Vec<Vec<i32>> = vec![];
+        transpose_in_place(&mut v);
+        assert!(v.is_empty());
+    }
+
+    #[test]
+    #[should_panic]
+    fn in_place_not_square() {
+        transpose_in_place(&mut [vec![1, 2], vec![3]]);
+    }
+}
@@ -0,0 +1,187 @@
+//! Derive macros for [`tlib`](https://github.com/T0mstone/tlib)
+//!
+//! These are re-exported by `tlib` when its `derive` feature is enabled.
+
+#![warn(missing_docs)]
+
+use proc_macro::TokenStream;
+use proc_macro2::{Span, TokenStream as TokenStream2};
+use quote::{format_ident, quote};
+use syn::{parse_macro_input, Data, DeriveInput, Error, Fields, Index, Member};
+
+/// Generates a struct-of-arrays companion type for a struct
+///
+/// For a struct `Record`, this generates a struct `RecordColumns`
+/// that has one `Vec` per field of `Record` (with the same names and visibilities).
+///
+/// `RecordColumns` gets the following methods (and a `Default` impl):
+/// - `new()` creates empty columns
+/// - `from_rows(Vec<Record>)` splits the rows into columns (like `vec_zip::unzip`)
+/// - `into_rows(self) -> Vec<Record>` joins the columns back into rows (like `vec_zip::zip`,
+///   so if the columns have different lengths, the result is as long as the shortest one)
+/// - `push(&mut self, Record)` appends a row
+/// - `get(&self, i)` returns a tuple of references to the fields of row `i`,
+///   or `None` if any column is too short
+/// - `len()` and `is_empty()` (of the shortest column)
+///
+/// Example:
+/// ```
+/// use tlib_derive::SoA;
+///
+/// #[derive(SoA, Debug, PartialEq)]
+/// struct Record {
+///     time: u64,
+///     value: f32,
+/// }
+///
+/// let mut cols = RecordColumns::from_rows(vec![
+///     Record { time: 0, value: 1.5 },
+///     Record { time: 1, value: 2.5 },
+/// ]);
+/// assert_eq!(cols.time, vec![0, 1]);
+/// assert_eq!(cols.value, vec![1.5, 2.5]);
+///
+/// cols.push(Record { time: 2, value: 3.5 });
+/// assert_eq!(cols.get(2), Some((&2, &3.5)));
+/// assert_eq!(cols.into_rows().len(), 3);
+/// ```
+#[proc_macro_derive(SoA)]
+pub fn derive_soa(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    soa_impl(input)
+        .unwrap_or_else(Error::into_compile_error)
+        .into()
+}
+
+fn soa_impl(input: DeriveInput) -> Result<TokenStream2, Error> {
+    let fields = match input.data {
+        Data::Struct(data) => data.fields,
+        _ => {
+            return Err(Error::new(
+                Span::call_site(),
+                "`SoA` can only be derived for structs",
+            ))
+        }
+    };
+    if fields.is_empty() {
+        return Err(Error::new(
+            Span::call_site(),
+            "`SoA` can only be derived for structs with at least one field",
+        ));
+    }
+
+    let vis = &input.vis;
+    let name = &input.ident;
+    let cols_name = format_ident!("{}Columns", name);
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    let generic_params = &input.generics.params;
+
+    let members: Vec<Member> = fields
+        .iter()
+        .enumerate()
+        .map(|(i, f)| match &f.ident {
+            Some(ident) => Member::Named(ident.clone()),
+            None => Member::Unnamed(Index::from(i)),
+        })
+        .collect();
+    // names for binding each field in patterns
+    let bindings: Vec<_> = (0..fields.len())
+        .map(|i| format_ident!("__field{}", i))
+        .collect();
+    let tys: Vec<_> = fields.iter().map(|f| &f.ty).collect();
+    let field_vis: Vec<_> = fields.iter().map(|f| &f.vis).collect();
+
+    let cols_def = match &fields {
+        Fields::Named(_) => quote! {
+            #vis struct #cols_name<#generic_params> #where_clause {
+                #(
+                    #[allow(missing_docs)]
+                    #field_vis #members: ::std::vec::Vec<#tys>,
+                )*
+            }
+        },
+        _ => quote! {
+            #vis struct #cols_name<#generic_params>(
+                #(
+                    #[allow(missing_docs)]
+                    #field_vis ::std::vec::Vec<#tys>,
+                )*
+            ) #where_clause;
+        },
+    };
+
+    let doc = format!("The columns of [`{}`], generated by `#[derive(SoA)]`", name);
+
+    Ok(quote! {
+        #[doc = #doc]
+        #cols_def
+
+        impl #impl_generics ::std::default::Default for #cols_name #ty_generics #where_clause {
+            fn default() -> Self {
+                Self {
+                    #(#members: ::std::vec::Vec::new(),)*
+                }
+            }
+        }
+
+        #[allow(dead_code)]
+        impl #impl_generics #cols_name #ty_generics #where_clause {
+            /// Creates empty columns
+            #vis fn new() -> Self {
+                ::std::default::Default::default()
+            }
+
+            /// Splits the rows into columns
+            #vis fn from_rows(rows: ::std::vec::Vec<#name #ty_generics>) -> Self {
+                let mut res = Self {
+                    #(#members: ::std::vec::Vec::with_capacity(rows.len()),)*
+                };
+                for row in rows {
+                    res.push(row);
+                }
+                res
+            }
+
+            /// Joins the columns into rows, stopping at the end of the shortest column
+            #vis fn into_rows(self) -> ::std::vec::Vec<#name #ty_generics> {
+                let len = self.len();
+                #(
+                    let mut #bindings = ::std::iter::IntoIterator::into_iter(self.#members);
+                )*
+                (0..len)
+                    .filter_map(|_| {
+                        ::std::option::Option::Some(#name {
+                            #(#members: #bindings.next()?,)*
+                        })
+                    })
+                    .collect()
+            }
+
+            /// Appends a row to the columns
+            #vis fn push(&mut self, row: #name #ty_generics) {
+                #(
+                    self.#members.push(row.#members);
+                )*
+            }
+
+            /// Returns references to the fields of the row at `index`
+            #vis fn get(&self, index: usize) -> ::std::option::Option<(#(&#tys,)*)> {
+                ::std::option::Option::Some((#(self.#members.get(index)?,)*))
+            }
+
+            /// The number of full rows (i.e. the length of the shortest column)
+            #vis fn len(&self) -> usize {
+                let mut len = usize::MAX;
+                #(
+                    len = len.min(self.#members.len());
+                )*
+                len
+            }
+
+            /// Whether there are no full rows
+            #vis fn is_empty(&self) -> bool {
+                self.len() == 0
+            }
+        }
+    })
+}
@@ -0,0 +1,63 @@
+use tlib_derive::SoA;
+
+#[derive(SoA, Debug, Clone, PartialEq)]
+struct Record<'a, T: Clone> {
+    name: &'a str,
+    value: T,
+    flag: bool,
+}
+
+#[derive(SoA, Debug, PartialEq)]
+struct Pair(u8, char);
+
+#[test]
+fn roundtrip() {
+    let rows = vec![
+        Record {
+            name: "a",
+            value: 1.0,
+            flag: true,
+        },
+        Record {
+            name: "b",
+            value: 2.0,
+            flag: false,
+        },
+    ];
+
+    let cols = RecordColumns::from_rows(rows.clone());
+    assert_eq!(cols.name, vec!["a", "b"]);
+    assert_eq!(cols.value, vec![1.0, 2.0]);
+    assert_eq!(cols.flag, vec![true, false]);
+    assert_eq!(cols.len(), 2);
+    assert_eq!(cols.get(1), Some((&"b", &2.0, &false)));
+    assert_eq!(cols.get(2), None);
+
+    assert_eq!(cols.into_rows(), rows);
+}
+
+#[test]
+fn uneven_columns() {
+    let mut cols = RecordColumns::new();
+    assert!(cols.is_empty());
+    cols.push(Record {
+        name: "a",
+        value: 0,
+        flag: true,
+    });
+    cols.value.push(1);
+
+    assert_eq!(cols.len(), 1);
+    assert_eq!(cols.get(1), None);
+    assert_eq!(cols.into_rows().len(), 1);
+}
+
+#[test]
+fn tuple_struct() {
+    let mut cols = PairColumns::from_rows(vec![Pair(0, 'a')]);
+    cols.push(Pair(1, 'b'));
+    assert_eq!(cols.0, vec![0, 1]);
+    assert_eq!(cols.1, vec!['a', 'b']);
+    assert_eq!(cols.get(0), Some((&0, &'a')));
+    assert_eq!(cols.into_rows(), vec![Pair(0, 'a'), Pair(1, 'b')]);
+}